@@ -1,6 +1,10 @@
+use crate::stats::hwmon::Hwmon;
 use byte_unit::{AdjustedByte, Byte, UnitType};
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
 use std::sync::OnceLock;
-use sysinfo::System;
+use sysinfo::{Disks, System, Users};
 
 static DEFAULT_BYTE: OnceLock<AdjustedByte> = OnceLock::new();
 //static SYSTEM: OnceLock<Mutex<System>> = OnceLock::new();
@@ -9,14 +13,47 @@ pub fn get_default_byte() -> &'static AdjustedByte {
     DEFAULT_BYTE.get_or_init(|| Byte::from_u64(0).get_appropriate_unit(UnitType::Binary))
 }
 
+// Format a byte rate with the same binary units used elsewhere, e.g. "1.50MiB/s"
+pub fn format_rate(bytes_per_sec: f64) -> String {
+    let rate = Byte::from_u64(bytes_per_sec as u64).get_appropriate_unit(UnitType::Binary);
+    format!("{:.2}{}/s", rate.get_value(), rate.get_unit())
+}
+
+// Helper function to read a file into a string
+pub fn read_file_to_string(path: &Path) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)?;
+    Ok(contents)
+}
+
 pub struct SysWrapper {
     pub sys: System,
+    // Shared hwmon view so every collector reads the same sensor snapshot
+    pub hwmon: Hwmon,
+    pub disks: Disks,
+    // uid -> user name for the process panels
+    pub users: Users,
 }
 
 impl SysWrapper {
     pub fn new() -> Self {
         SysWrapper {
             sys: System::new_all(),
+            hwmon: Hwmon::new(),
+            disks: Disks::new_with_refreshed_list(),
+            users: Users::new_with_refreshed_list(),
+        }
+    }
+
+    // Nothing read from the system yet, for tests that must not depend on the host
+    #[cfg(test)]
+    pub fn empty() -> Self {
+        SysWrapper {
+            sys: System::new(),
+            hwmon: Hwmon::new(),
+            disks: Disks::new(),
+            users: Users::new(),
         }
     }
 }
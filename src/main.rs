@@ -2,8 +2,19 @@ use common::SysWrapper;
 use crossterm::event::{self, Event, KeyCode, KeyModifiers};
 use renderer::core::Rat;
 use stats::cpu::CpuStats; // Add this line
+use stats::disk::DiskStats;
+use stats::fan::FanStats;
 use stats::gpu::GpuStats;
+use stats::hwmon::SensorStats;
 use stats::mem::MemStats;
+use stats::net::NetStats;
+use stats::power_supply::PowerSupplyStats;
+use stats::process::ProcessStats;
+use stats::process_tree::ProcessTreeStats;
+use stats::psi::PsiStats;
+use stats::storage::StorageStats;
+use stats::thermal::ThermalStats;
+use stats::voltage::VoltageStats;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -13,13 +24,28 @@ mod renderer;
 mod stats;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let voltage_tolerance = parse_voltage_tolerance()?;
     let mut sys: SysWrapper = SysWrapper::new();
     let mut cpustats: CpuStats = CpuStats::new(); // Add this line
     let mut memstats: MemStats = MemStats::new();
     let mut gpustats: GpuStats = GpuStats::new();
+    let mut sensorstats: SensorStats = SensorStats::new();
+    let mut fanstats: FanStats = FanStats::new();
+    let mut netstats: NetStats = NetStats::new();
+    let mut diskstats: DiskStats = DiskStats::new();
+    let mut psistats: PsiStats = PsiStats::new();
+    let mut powersupplystats: PowerSupplyStats = PowerSupplyStats::new();
+    let mut thermalstats: ThermalStats = ThermalStats::new();
+    let mut storagestats: StorageStats = StorageStats::new();
+    let mut voltagestats: VoltageStats = VoltageStats::new(voltage_tolerance);
+    let mut processstats: ProcessStats = ProcessStats::new();
+    let mut processtreestats: ProcessTreeStats = ProcessTreeStats::new();
     let mut rat: Rat = Rat::new();
     let running = Arc::new(AtomicBool::new(true));
     let r = running.clone();
+    // The process panels are too tall to fit below the sensors, so they get a view of
+    // their own, and the sort and scroll keys only act while it is shown
+    let mut show_processes = false;
     let tick_rate = Duration::from_millis(250);
     let mut last_tick = Instant::now();
 
@@ -37,23 +63,99 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 {
                     break;
                 }
+
+                // Toggle loopback and other virtual network interfaces
+                if key.code == KeyCode::Char('v') {
+                    netstats.toggle_virtual();
+                }
+
+                // Switch between the sensor panels and the process view
+                if key.code == KeyCode::Char('p') {
+                    show_processes = !show_processes;
+                    if show_processes {
+                        processstats.reset_rates();
+                    }
+                }
+
+                // Sort and scroll the process table
+                if show_processes {
+                    match key.code {
+                        KeyCode::Left => processstats.move_sort_column(-1),
+                        KeyCode::Right => processstats.move_sort_column(1),
+                        KeyCode::Char('r') => processstats.toggle_sort_order(),
+                        KeyCode::Up => processstats.scroll_by(-1),
+                        KeyCode::Down => processstats.scroll_by(1),
+                        KeyCode::PageUp => {
+                            processstats.scroll_by(-(processstats.page_rows() as isize))
+                        }
+                        KeyCode::PageDown => {
+                            processstats.scroll_by(processstats.page_rows() as isize)
+                        }
+                        KeyCode::Home => processstats.scroll_to_top(),
+                        KeyCode::End => processstats.scroll_to_bottom(),
+                        _ => {}
+                    }
+                }
             }
         }
 
         // Update UI on tick
         if last_tick.elapsed() >= tick_rate {
             // Update stats
+            // Sensors go first so the hwmon readings are fresh for the other collectors
+            sensorstats.update(&mut sys);
             cpustats.update(&mut sys); // Add this line
             memstats.update(&mut sys);
             gpustats.update(&mut sys);
+            fanstats.update(&mut sys, gpustats.get_gpus());
+            netstats.update(&mut sys);
+            diskstats.update(&mut sys);
+            psistats.update(&mut sys);
+            powersupplystats.update(&mut sys);
+            thermalstats.update(&mut sys);
+            storagestats.update(&mut sys);
+            voltagestats.update(&mut sys);
+            // Walking every process is the most expensive refresh, so only do it while
+            // the process view is shown
+            if show_processes {
+                fit_process_view(
+                    cpustats.get_mouse().content_height(),
+                    &mut processstats,
+                    &mut processtreestats,
+                )?;
+                processstats.update(&mut sys);
+                // Uses the process list refreshed just above
+                processtreestats.update(&mut sys);
+            }
 
             // Clear previous mice
             rat.clear();
 
             // Add new mice - Add CPU stats first, above memory
             rat.add(cpustats.get_mouse()); // Add this line
-            rat.add(memstats.get_mouse());
-            rat.add(gpustats.get_mouse());
+            if show_processes {
+                rat.add(processstats.get_mouse());
+                rat.add(processtreestats.get_mouse());
+                rat.set_help("p to go back to the sensors, ←/→ and r to sort, ↑/↓/PgUp/PgDn/Home/End to scroll, ctrl-c or q to quit");
+            } else {
+                rat.add(memstats.get_mouse());
+                rat.add(diskstats.get_mouse());
+                rat.add(storagestats.get_mouse());
+                rat.add(psistats.get_mouse());
+                // Desktops and servers have no battery, so skip the panel entirely
+                if powersupplystats.has_battery() {
+                    rat.add(powersupplystats.get_mouse());
+                }
+                rat.add(gpustats.get_mouse());
+                rat.add(netstats.get_mouse());
+                rat.add(fanstats.get_mouse());
+                rat.add(voltagestats.get_mouse());
+                rat.add(sensorstats.get_mouse());
+                rat.add(thermalstats.get_mouse());
+                rat.set_help(
+                    "v to toggle virtual interfaces, p to show processes, ctrl-c or q to quit",
+                );
+            }
 
             // Draw the UI
             rat.draw()?;
@@ -64,3 +166,41 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+// Size the process table and tree to the lines left below the CPU panel: side by side
+// when the terminal is wide enough for both, stacked otherwise
+fn fit_process_view(
+    cpu_height: usize,
+    processstats: &mut ProcessStats,
+    processtreestats: &mut ProcessTreeStats,
+) -> std::io::Result<()> {
+    let (width, height) = crossterm::terminal::size()?;
+    // Margin, title and footer; panels are packed with 4 columns of padding each
+    let free = (height as usize).saturating_sub(cpu_height + 4);
+    let packed_width = ProcessStats::width() + ProcessTreeStats::width() + 8;
+    if packed_width <= (width as usize).saturating_sub(2) {
+        processstats.set_height(free);
+        processtreestats.set_height(free);
+    } else {
+        processstats.set_height(free / 2);
+        processtreestats.set_height(free - free / 2);
+    }
+    Ok(())
+}
+
+// `--voltage-tolerance <percent>` flags rails that drift further than this from nominal
+fn parse_voltage_tolerance() -> Result<Option<f64>, Box<dyn std::error::Error>> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let value = match arg.strip_prefix("--voltage-tolerance") {
+            Some("") => args.next(),
+            Some(rest) if rest.starts_with('=') => Some(rest[1..].to_string()),
+            _ => continue,
+        };
+        return match value.and_then(|value| value.parse::<f64>().ok()) {
+            Some(percent) if percent > 0.0 => Ok(Some(percent)),
+            _ => Err("--voltage-tolerance expects a positive percentage".into()),
+        };
+    }
+    Ok(None)
+}
@@ -10,6 +10,8 @@ pub struct Rat {
     hole: Terminal<CrosstermBackend<std::io::Stdout>>,
     mice: Vec<Mouse>,
     color_shift_counter: usize,
+    // Key hints in the footer, which depend on the current view
+    help: String,
 }
 
 #[derive(PartialEq, Clone)]
@@ -24,9 +26,14 @@ impl Rat {
             hole: ratatui::init(),
             mice: Vec::new(),
             color_shift_counter: 0,
+            help: String::new(),
         }
     }
 
+    pub fn set_help(&mut self, help: &str) {
+        self.help = help.to_string();
+    }
+
     pub fn add(&mut self, mouse: Mouse) {
         self.mice.push(mouse);
     }
@@ -35,10 +42,6 @@ impl Rat {
         self.mice.clear();
     }
 
-    pub fn remove(&mut self, mouse: &Mouse) {
-        self.mice.retain(|x| x != mouse);
-    }
-
     // In src/renderer/core.rs
     // Inside the draw method of the Rat struct, after rendering the mice but before the final closing brace
     pub fn draw(&mut self) -> std::io::Result<()> {
@@ -46,9 +49,10 @@ impl Rat {
         self.color_shift_counter = (self.color_shift_counter + 1) % 7;
 
         let mice = self.mice.clone();
+        let help = self.help.clone();
         self.hole.draw(|frame| {
             // Get available area
-            let area = frame.size();
+            let area = frame.area();
 
             // Create a vertical layout with title, main content, and footer
             let vertical_layout = Layout::default()
@@ -106,25 +110,48 @@ impl Rat {
 
             // Calculate heights for CPU section
             let cpu_height = cpu_mouse
-                .map(|mouse| mouse.content.len() as u16 + 2) // +2 for borders
+                .map(|mouse| mouse.content_height() as u16)
                 .unwrap_or(0);
 
-            // Calculate height for horizontal section (max height of remaining mice)
-            let horizontal_section_height = mice
-                .iter()
-                .skip(1) // Skip the CPU mouse
-                .map(|mouse| mouse.content.len() as u16 + 2) // +2 for borders
-                .max()
-                .unwrap_or(0);
+            // Pack the remaining mice into horizontal rows, starting a new row whenever
+            // the next mouse would not fit the terminal width
+            let mut rows: Vec<Vec<&Mouse>> = Vec::new();
+            let mut row_width = 0;
+            for mouse in mice.iter().skip(1) {
+                // Add padding for borders
+                let width = mouse.content_width() + 4;
+                match rows.last_mut() {
+                    Some(row) if row_width + width <= main_area.width as usize => {
+                        row.push(mouse);
+                        row_width += width;
+                    }
+                    _ => {
+                        rows.push(vec![mouse]);
+                        row_width = width;
+                    }
+                }
+            }
+
+            // Content layout within main area: CPU, then each row at the height of its
+            // tallest mouse, shrunk to fit when the terminal is too short for all of them
+            let mut heights = vec![cpu_height];
+            for row in &rows {
+                let row_height = row
+                    .iter()
+                    .map(|mouse| mouse.content_height() as u16)
+                    .max()
+                    .unwrap_or(0);
+                heights.push(row_height);
+            }
+            let mut constraints: Vec<Constraint> = fit_heights(&heights, main_area.height)
+                .into_iter()
+                .map(Constraint::Length)
+                .collect();
+            constraints.push(Constraint::Min(0)); // Remaining space
 
-            // Content layout within main area
             let main_chunks = Layout::default()
                 .direction(Direction::Vertical)
-                .constraints([
-                    Constraint::Length(cpu_height),
-                    Constraint::Length(horizontal_section_height),
-                    Constraint::Min(0), // Remaining space
-                ])
+                .constraints(constraints)
                 .split(main_area);
 
             // Render CPU mouse at the top if present
@@ -133,44 +160,26 @@ impl Rat {
                 frame.render_widget(paragraph, main_chunks[0]);
             }
 
-            // Create horizontal layout for the remaining mice (memory and GPU)
-            let remaining_mice = mice.iter().skip(1).collect::<Vec<_>>();
-
-            if !remaining_mice.is_empty() {
-                let horizontal_constraints: Vec<Constraint> = remaining_mice
+            // Render each row of the remaining mice horizontally
+            for (row_idx, row) in rows.iter().enumerate() {
+                let horizontal_constraints: Vec<Constraint> = row
                     .iter()
-                    .map(|mouse| {
-                        // Find the longest line in the content
-                        let max_width = mouse
-                            .content
-                            .iter()
-                            .map(|line| line.width())
-                            .max()
-                            .unwrap_or(0)
-                            .max(mouse.title.len());
-
-                        // Add padding for borders
-                        Constraint::Min((max_width + 4) as u16)
-                    })
+                    .map(|mouse| Constraint::Min((mouse.content_width() + 4) as u16))
                     .collect();
 
                 let horizontal_chunks = Layout::default()
                     .direction(Direction::Horizontal)
                     .constraints(horizontal_constraints)
-                    .split(main_chunks[1]);
+                    .split(main_chunks[row_idx + 1]);
 
-                // Render the remaining mice horizontally
-                for (idx, mouse) in remaining_mice.iter().enumerate() {
-                    if idx < horizontal_chunks.len() {
-                        let paragraph = mouse.get_paragraph();
-                        frame.render_widget(paragraph, horizontal_chunks[idx]);
-                    }
+                for (idx, mouse) in row.iter().enumerate() {
+                    let paragraph = mouse.get_paragraph();
+                    frame.render_widget(paragraph, horizontal_chunks[idx]);
                 }
             }
 
             // Add help text in the footer area - aligned to the right
-            let help_text =
-                Paragraph::new("ctrl-c or q to quit").alignment(ratatui::layout::Alignment::Right);
+            let help_text = Paragraph::new(help).alignment(ratatui::layout::Alignment::Right);
             frame.render_widget(help_text, footer_area);
         })?;
 
@@ -189,10 +198,41 @@ impl Rat {
     }
 }
 
+// Shrink section heights to fit the available lines: short sections keep their height
+// and the tall ones share what is left, so every panel stays at least partly visible
+fn fit_heights(heights: &[u16], available: u16) -> Vec<u16> {
+    let fitted_total =
+        |cap: u16| -> u32 { heights.iter().map(|height| (*height).min(cap) as u32).sum() };
+    let tallest = heights.iter().copied().max().unwrap_or(0);
+    if fitted_total(tallest) <= available as u32 {
+        return heights.to_vec();
+    }
+
+    // Largest height every section can be cut down to
+    let mut cap = 0;
+    while cap < tallest && fitted_total(cap + 1) <= available as u32 {
+        cap += 1;
+    }
+
+    // Hand the lines left below the cap to the cut sections, top first
+    let mut spare = available as u32 - fitted_total(cap);
+    let mut fitted: Vec<u16> = heights.iter().map(|height| (*height).min(cap)).collect();
+    for (fitted, height) in fitted.iter_mut().zip(heights) {
+        if spare == 0 {
+            break;
+        }
+        if *fitted < *height {
+            *fitted += 1;
+            spare -= 1;
+        }
+    }
+    fitted
+}
+
 impl Mouse {
     pub fn new(title: String) -> Self {
         Mouse {
-            title: title,
+            title,
             content: Vec::new(),
         }
     }
@@ -202,7 +242,12 @@ impl Mouse {
         self.content.push(line);
     }
 
-    pub fn get_paragraph(&self) -> Paragraph {
+    // Add a pre-styled line, e.g. one with coloured spans
+    pub fn add_line(&mut self, line: Line<'static>) {
+        self.content.push(line);
+    }
+
+    pub fn get_paragraph(&self) -> Paragraph<'_> {
         Paragraph::new(self.content.clone()).block(
             Block::default()
                 .borders(Borders::ALL)
@@ -219,8 +264,35 @@ impl Mouse {
             .max(self.title.len())
     }
 
+    // Plain text of the panel, one line per row
+    #[cfg(test)]
+    pub fn text(&self) -> String {
+        self.content
+            .iter()
+            .map(|line| line.to_string())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
     // Add this method to get the number of lines
     pub fn content_height(&self) -> usize {
         self.content.len() + 2 // +2 for title bar and bottom border
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fit_heights_keeps_sections_that_fit() {
+        assert_eq!(fit_heights(&[10, 20, 5], 40), vec![10, 20, 5]);
+    }
+
+    #[test]
+    fn fit_heights_cuts_the_tall_sections_first() {
+        // A 45-line Sensors panel must not push the short rows below it off-screen
+        assert_eq!(fit_heights(&[10, 45, 6, 12], 40), vec![10, 12, 6, 12]);
+        assert_eq!(fit_heights(&[30, 30, 30], 40), vec![14, 13, 13]);
+    }
+}
@@ -1,47 +1,124 @@
-use crate::{common::SysWrapper, renderer::core::Mouse};
-use std::fs::File;
-use std::io::{self, Read};
-use std::path::{Path, PathBuf};
+use crate::{
+    common::{read_file_to_string, SysWrapper},
+    renderer::core::Mouse,
+    stats::{
+        hwmon::{Hwmon, SensorKind},
+        power::PowerStats,
+        topology::CpuTopology,
+    },
+};
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+};
+use std::collections::BTreeMap;
+use std::path::Path;
 
 #[derive(Clone)]
 pub struct CpuStats {
     name: String,
+    socket_count: usize,
+    die_count: usize,
     core_count: usize,
     thread_count: usize,
+    topology: CpuTopology,
     overall_usage: f32,
-    core_usage: Vec<f32>,
+    // Keyed by CPU id, since sysinfo only lists the online CPUs
+    core_usage: BTreeMap<usize, f32>,
     tctl_temp: f32,
-    tccd1_temp: f32,
+    // One entry per CCD (Tccd1..Tccd12 on large Ryzen/Threadripper/EPYC parts)
+    ccd_temps: Vec<CcdTemp>,
     composite_temp: f32,
     min_usage: f32,
     max_usage: f32,
     min_tctl_temp: f32,
     max_tctl_temp: f32,
-    min_tccd1_temp: f32,
-    max_tccd1_temp: f32,
     min_composite_temp: f32,
     max_composite_temp: f32,
+    // Per physical core temperatures (Intel coretemp), indexed like topology.cores
+    core_temps: Vec<Option<CoreTemp>>,
+    hottest_core: Option<usize>,
+    min_hottest_temp: f32,
+    max_hottest_temp: f32,
+    // Frequencies in MHz, keyed by logical CPU id (ids can have gaps when CPUs are offline)
+    core_freq: BTreeMap<usize, CoreFrequency>,
+    avg_freq: f32,
+    min_avg_freq: f32,
+    max_avg_freq: f32,
+    governor: String,
+    energy_performance_preference: String,
+    power: PowerStats,
+}
+
+#[derive(Clone)]
+struct CcdTemp {
+    ccd: u32,
+    temp: f32,
+    min_temp: f32,
+    max_temp: f32,
+}
+
+#[derive(Clone, Copy)]
+struct CoreTemp {
+    temp: f32,
+    // coretemp's "high" threshold and the critical (throttle/shutdown) threshold
+    high: Option<f32>,
+    crit: Option<f32>,
+}
+
+impl CoreTemp {
+    fn style(&self) -> Style {
+        match (self.high, self.crit) {
+            (_, Some(crit)) if self.temp >= crit => Style::default().fg(Color::Red),
+            (Some(high), _) if self.temp >= high => Style::default().fg(Color::Yellow),
+            // Without a high threshold, warn within 10°C of critical
+            (None, Some(crit)) if self.temp >= crit - 10.0 => Style::default().fg(Color::Yellow),
+            _ => Style::default().fg(Color::Green),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct CoreFrequency {
+    current: f32,
+    // Hardware limits from cpuinfo_{min,max}_freq; P and E cores differ on hybrid parts
+    hw_min: f32,
+    hw_max: f32,
+    min: f32,
+    max: f32,
 }
 
 impl CpuStats {
     pub fn new() -> Self {
         CpuStats {
             name: String::from("Unknown CPU"),
+            socket_count: 0,
+            die_count: 0,
             core_count: 0,
             thread_count: 0,
+            topology: CpuTopology::new(),
             overall_usage: 0.0,
-            core_usage: Vec::new(),
+            core_usage: BTreeMap::new(),
             tctl_temp: 0.0,
-            tccd1_temp: 0.0,
+            ccd_temps: Vec::new(),
             composite_temp: 0.0,
             min_usage: 0.0,
             max_usage: 0.0,
             min_tctl_temp: 0.0,
             max_tctl_temp: 0.0,
-            min_tccd1_temp: 0.0,
-            max_tccd1_temp: 0.0,
             min_composite_temp: 0.0,
             max_composite_temp: 0.0,
+            core_temps: Vec::new(),
+            hottest_core: None,
+            min_hottest_temp: 0.0,
+            max_hottest_temp: 0.0,
+            core_freq: BTreeMap::new(),
+            avg_freq: 0.0,
+            min_avg_freq: 0.0,
+            max_avg_freq: 0.0,
+            governor: String::new(),
+            energy_performance_preference: String::new(),
+            power: PowerStats::new(),
         }
     }
 
@@ -60,8 +137,19 @@ impl CpuStats {
         // Get core count and thread count
         // Note: in sysinfo, the CPU count is actually the thread count
         self.thread_count = sys_obj.sys.cpus().len();
-        // Estimate core count (may not be accurate for all CPUs)
-        self.core_count = self.thread_count / 2;
+
+        self.topology.refresh();
+
+        if self.topology.is_empty() {
+            // No sysfs topology (e.g. in some containers), fall back to an SMT estimate
+            self.socket_count = 1;
+            self.die_count = 1;
+            self.core_count = (self.thread_count / 2).max(1);
+        } else {
+            self.socket_count = self.topology.socket_count();
+            self.die_count = self.topology.die_count();
+            self.core_count = self.topology.core_count();
+        }
 
         // Get overall CPU usage
         let global_usage = sys_obj.sys.global_cpu_usage();
@@ -75,26 +163,113 @@ impl CpuStats {
             self.max_usage = self.overall_usage;
         }
 
-        // Get per-core usage
+        // Get per-core usage; names are "cpuN", so an offline CPU leaves a gap instead of
+        // shifting every later one
         self.core_usage = sys_obj
             .sys
             .cpus()
             .iter()
-            .map(|cpu| cpu.cpu_usage())
+            .enumerate()
+            .map(|(i, cpu)| {
+                let id = cpu
+                    .name()
+                    .strip_prefix("cpu")
+                    .and_then(|id| id.parse::<usize>().ok())
+                    .unwrap_or(i);
+                (id, cpu.cpu_usage())
+            })
             .collect();
 
+        // Update clock speeds
+        self.update_frequencies();
+
         // Update temperature information
-        self.update_temperatures();
+        self.update_temperatures(&sys_obj.hwmon);
+
+        // Update RAPL and hwmon power readings
+        self.power.update(&sys_obj.hwmon);
     }
 
-    fn update_temperatures(&mut self) {
+    fn update_frequencies(&mut self) {
+        let cpu_path = Path::new("/sys/devices/system/cpu");
+        let read_khz = |cpu: usize, file: &str| -> Option<f32> {
+            let path = cpu_path.join(format!("cpu{}/cpufreq/{}", cpu, file));
+            let khz = read_file_to_string(&path)
+                .ok()?
+                .trim()
+                .parse::<f32>()
+                .ok()?;
+            Some(khz / 1000.0)
+        };
+
+        let cpu_ids: Vec<usize> = if self.topology.is_empty() {
+            (0..self.thread_count).collect()
+        } else {
+            self.topology.cpus.iter().map(|cpu| cpu.id).collect()
+        };
+
+        let mut core_freq = BTreeMap::new();
+        for cpu in cpu_ids {
+            // Machines without cpufreq (many VMs) simply report nothing
+            let Some(current) = read_khz(cpu, "scaling_cur_freq") else {
+                continue;
+            };
+
+            let (min, max) = match self.core_freq.get(&cpu) {
+                Some(previous) => (previous.min.min(current), previous.max.max(current)),
+                None => (current, current),
+            };
+            core_freq.insert(
+                cpu,
+                CoreFrequency {
+                    current,
+                    hw_min: read_khz(cpu, "cpuinfo_min_freq").unwrap_or_default(),
+                    hw_max: read_khz(cpu, "cpuinfo_max_freq").unwrap_or_default(),
+                    min,
+                    max,
+                },
+            );
+        }
+        self.core_freq = core_freq;
+
+        if self.core_freq.is_empty() {
+            return;
+        }
+
+        self.avg_freq = self
+            .core_freq
+            .values()
+            .map(|freq| freq.current)
+            .sum::<f32>()
+            / self.core_freq.len() as f32;
+
+        // Update min/max for average frequency
+        if self.min_avg_freq == 0.0 || self.avg_freq < self.min_avg_freq {
+            self.min_avg_freq = self.avg_freq;
+        }
+        if self.avg_freq > self.max_avg_freq {
+            self.max_avg_freq = self.avg_freq;
+        }
+
+        // Governor and EPP are normally identical across CPUs, so cpu0 is representative
+        let read_policy = |file: &str| {
+            read_file_to_string(&cpu_path.join(format!("cpu0/cpufreq/{}", file)))
+                .map(|value| value.trim().to_string())
+                .unwrap_or_default()
+        };
+        self.governor = read_policy("scaling_governor");
+        self.energy_performance_preference = read_policy("energy_performance_preference");
+    }
+
+    fn update_temperatures(&mut self, hwmon: &Hwmon) {
         // Try to get temps from various sources based on CPU type
-        // First, try lm_sensors style paths for AMD CPUs
-        self.update_amd_temperatures();
+        // First, try the k10temp driver used by AMD CPUs
+        self.update_amd_temperatures(hwmon);
 
-        // If we didn't get temps from AMD, try Intel sensors
-        if self.tctl_temp == 0.0 {
-            self.update_intel_temperatures();
+        // If there is no k10temp chip, try Intel sensors. This checks for the chip rather than
+        // tctl_temp, which stays set after the first tick and would freeze Intel readings
+        if hwmon.chips_named("k10temp").next().is_none() {
+            self.update_intel_temperatures(hwmon);
         }
 
         // Update min/max temperatures
@@ -107,12 +282,14 @@ impl CpuStats {
             }
         }
 
-        if self.tccd1_temp > 0.0 {
-            if self.min_tccd1_temp == 0.0 || self.tccd1_temp < self.min_tccd1_temp {
-                self.min_tccd1_temp = self.tccd1_temp;
-            }
-            if self.tccd1_temp > self.max_tccd1_temp {
-                self.max_tccd1_temp = self.tccd1_temp;
+        for ccd in &mut self.ccd_temps {
+            if ccd.temp > 0.0 {
+                if ccd.min_temp == 0.0 || ccd.temp < ccd.min_temp {
+                    ccd.min_temp = ccd.temp;
+                }
+                if ccd.temp > ccd.max_temp {
+                    ccd.max_temp = ccd.temp;
+                }
             }
         }
 
@@ -126,130 +303,119 @@ impl CpuStats {
         }
     }
 
-    fn update_amd_temperatures(&mut self) {
+    fn update_amd_temperatures(&mut self, hwmon: &Hwmon) {
         // For AMD CPUs, check k10temp sensors
-        let hwmon_path = Path::new("/sys/class/hwmon");
-        if !hwmon_path.exists() {
-            return;
-        }
+        if let Some(chip) = hwmon.chips_named("k10temp").next() {
+            // Based on Psensor screenshot, looking for Tctl and the Tccd channels
+
+            // Tctl is usually temp1, but prefer the labelled channel when present
+            if let Some(tctl) = chip
+                .find_sensor(SensorKind::Temperature, "Tctl")
+                .or_else(|| chip.sensor(SensorKind::Temperature, 1))
+            {
+                self.tctl_temp = tctl.value as f32;
+            }
 
-        if let Ok(hwmon_entries) = std::fs::read_dir(hwmon_path) {
-            for entry in hwmon_entries.filter_map(Result::ok) {
-                let hwmon_dir = entry.path();
-
-                // Check if this is an AMD k10temp sensor
-                let name_path = hwmon_dir.join("name");
-                if name_path.exists() {
-                    if let Ok(name) = read_file_to_string(&name_path) {
-                        let name = name.trim();
-
-                        // Look specifically for k10temp which contains AMD CPU sensors
-                        if name == "k10temp" {
-                            // Based on Psensor screenshot, looking for Tctl and tccd1
-
-                            // Check for Tctl (usually temp1_input)
-                            let tctl_path = hwmon_dir.join("temp1_input");
-                            if tctl_path.exists() {
-                                if let Ok(temp_str) = read_file_to_string(&tctl_path) {
-                                    if let Ok(temp_millicelsius) = temp_str.trim().parse::<f32>() {
-                                        self.tctl_temp = temp_millicelsius / 1000.0;
-                                    }
-                                }
-                            }
-
-                            // Check specifically for tccd1 (usually temp2_input but can be in other locations)
-                            // Try multiple possible paths
-                            let possible_tccd1_paths = [
-                                hwmon_dir.join("temp2_input"),
-                                hwmon_dir.join("temp3_input"),
-                                hwmon_dir.join("temp4_input"),
-                            ];
-
-                            // Try each path
-                            for path in &possible_tccd1_paths {
-                                if path.exists() {
-                                    // Check if this is tccd1 by looking for a label file
-                                    let label_path = path.with_file_name(
-                                        path.file_name()
-                                            .unwrap()
-                                            .to_str()
-                                            .unwrap()
-                                            .replace("_input", "_label"),
-                                    );
-
-                                    let is_tccd1 = if label_path.exists() {
-                                        // If we have a label file, check if it contains "Tccd1"
-                                        if let Ok(label) = read_file_to_string(&label_path) {
-                                            label.to_lowercase().contains("ccd1")
-                                                || label.to_lowercase().contains("tccd1")
-                                        } else {
-                                            false
-                                        }
-                                    } else {
-                                        // If no label, assume temp2_input is tccd1 (common for k10temp)
-                                        path.to_str().unwrap().ends_with("temp2_input")
-                                    };
-
-                                    if is_tccd1 {
-                                        if let Ok(temp_str) = read_file_to_string(path) {
-                                            if let Ok(temp_millicelsius) =
-                                                temp_str.trim().parse::<f32>()
-                                            {
-                                                self.tccd1_temp = temp_millicelsius / 1000.0;
-                                                break;
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-
-                            // Use Tctl as composite if we don't have a better value
-                            self.composite_temp = self.tctl_temp;
-                            break;
-                        }
-                    }
+            // Tccd channels are labelled "Tccd1".."Tccd12" on recent kernels
+            let mut ccds: Vec<(u32, f32)> = chip
+                .sensors_of(SensorKind::Temperature)
+                .filter_map(|sensor| {
+                    let label = sensor.label.to_lowercase();
+                    let ccd = label.strip_prefix("tccd")?.parse::<u32>().ok()?;
+                    Some((ccd, sensor.value as f32))
+                })
+                .collect();
+
+            // Without labels assume temp2 is Tccd1
+            if ccds.is_empty() {
+                if let Some(sensor) = chip
+                    .sensor(SensorKind::Temperature, 2)
+                    .filter(|sensor| sensor.label == "temp2")
+                {
+                    ccds.push((1, sensor.value as f32));
                 }
             }
+            ccds.sort_by_key(|(ccd, _)| *ccd);
+
+            // Keep the min/max history of CCDs we already know about
+            self.ccd_temps = ccds
+                .into_iter()
+                .map(|(ccd, temp)| {
+                    let previous = self.ccd_temps.iter().find(|c| c.ccd == ccd);
+                    CcdTemp {
+                        ccd,
+                        temp,
+                        min_temp: previous.map_or(0.0, |c| c.min_temp),
+                        max_temp: previous.map_or(0.0, |c| c.max_temp),
+                    }
+                })
+                .collect();
+
+            // Use Tctl as composite if we don't have a better value
+            self.composite_temp = self.tctl_temp;
         }
     }
 
-    fn update_intel_temperatures(&mut self) {
-        // For Intel CPUs, check coretemp sensors
-        let hwmon_path = Path::new("/sys/class/hwmon");
-        if !hwmon_path.exists() {
-            return;
-        }
+    fn update_intel_temperatures(&mut self, hwmon: &Hwmon) {
+        self.core_temps = vec![None; self.topology.cores.len()];
+
+        // For Intel CPUs, check coretemp sensors (one chip per package)
+        for (i, chip) in hwmon.chips_named("coretemp").enumerate() {
+            // Package temperature (similar to composite) is temp1, labelled "Package id N"
+            let package = chip.sensor(SensorKind::Temperature, 1);
+            let package_id = package
+                .and_then(|sensor| sensor.label.strip_prefix("Package id "))
+                .and_then(|id| id.trim().parse::<i32>().ok())
+                .unwrap_or(i as i32);
+
+            if i == 0 {
+                if let Some(package) = package {
+                    self.composite_temp = package.value as f32;
+                    // For Intel, we'll use package temp as Tctl equivalent
+                    self.tctl_temp = self.composite_temp;
+                }
+            }
 
-        if let Ok(hwmon_entries) = std::fs::read_dir(hwmon_path) {
-            for entry in hwmon_entries.filter_map(Result::ok) {
-                let hwmon_dir = entry.path();
-
-                // Check if this is an Intel coretemp sensor
-                let name_path = hwmon_dir.join("name");
-                if name_path.exists() {
-                    if let Ok(name) = read_file_to_string(&name_path) {
-                        let name = name.trim();
-
-                        if name == "coretemp" {
-                            // This is the Intel CPU temp sensor
-                            // Package temperature (similar to composite)
-                            let package_path = hwmon_dir.join("temp1_input");
-                            if package_path.exists() {
-                                if let Ok(temp_str) = read_file_to_string(&package_path) {
-                                    if let Ok(temp_millicelsius) = temp_str.trim().parse::<f32>() {
-                                        self.composite_temp = temp_millicelsius / 1000.0;
-                                        // For Intel, we'll use package temp as Tctl equivalent
-                                        self.tctl_temp = self.composite_temp;
-                                    }
-                                }
-                            }
-
-                            break;
-                        }
+            // "Core N" labels carry the core_id of the physical core
+            for sensor in chip.sensors_of(SensorKind::Temperature) {
+                let Some(core_id) = sensor
+                    .label
+                    .strip_prefix("Core ")
+                    .and_then(|id| id.trim().parse::<i32>().ok())
+                else {
+                    continue;
+                };
+
+                // Hybrid parts can reuse core_id across clusters; all share this reading
+                for (index, core) in self.topology.cores.iter().enumerate() {
+                    if core.package_id == package_id && core.core_id == core_id {
+                        self.core_temps[index] = Some(CoreTemp {
+                            temp: sensor.value as f32,
+                            high: sensor.max_limit.map(|limit| limit as f32),
+                            crit: sensor.crit_limit.map(|limit| limit as f32),
+                        });
                     }
                 }
             }
         }
+
+        self.hottest_core = self
+            .core_temps
+            .iter()
+            .enumerate()
+            .filter_map(|(index, temp)| temp.map(|temp| (index, temp.temp)))
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(index, _)| index);
+
+        // Update min/max for the hottest core
+        if let Some(temp) = self.hottest_core.and_then(|index| self.core_temps[index]) {
+            if self.min_hottest_temp == 0.0 || temp.temp < self.min_hottest_temp {
+                self.min_hottest_temp = temp.temp;
+            }
+            if temp.temp > self.max_hottest_temp {
+                self.max_hottest_temp = temp.temp;
+            }
+        }
     }
 
     pub fn get_mouse(&self) -> Mouse {
@@ -259,10 +425,15 @@ impl CpuStats {
         // Add CPU model name
         mouse.add(format!("Model: {}", self.name));
 
-        // Add core and thread count
+        // Add socket, die, core and thread count
+        let smt = match self.topology.smt_active {
+            Some(true) => " (SMT on)",
+            Some(false) => " (SMT off)",
+            None => "",
+        };
         mouse.add(format!(
-            "Cores: {}, Threads: {}",
-            self.core_count, self.thread_count
+            "Sockets: {}, Dies: {}, Cores: {}, Threads: {}{}",
+            self.socket_count, self.die_count, self.core_count, self.thread_count, smt
         ));
 
         // Add overall CPU usage with min/max
@@ -271,6 +442,43 @@ impl CpuStats {
             self.overall_usage, self.min_usage, self.max_usage
         ));
 
+        // Add average clock speed with min/max and the hardware range
+        if !self.core_freq.is_empty() {
+            let hw_min = self
+                .core_freq
+                .values()
+                .map(|freq| freq.hw_min)
+                .fold(f32::MAX, f32::min);
+            let hw_max = self
+                .core_freq
+                .values()
+                .map(|freq| freq.hw_max)
+                .fold(0.0, f32::max);
+            mouse.add(format!(
+                "Freq: {:.0} MHz avg (Min: {:.0} MHz, Max: {:.0} MHz) Range: {:.0}-{:.0} MHz",
+                self.avg_freq, self.min_avg_freq, self.max_avg_freq, hw_min, hw_max
+            ));
+
+            let peak = self
+                .core_freq
+                .values()
+                .map(|freq| freq.max)
+                .fold(0.0, f32::max);
+            let low = self
+                .core_freq
+                .values()
+                .map(|freq| freq.min)
+                .fold(f32::MAX, f32::min);
+            let mut policy = format!(
+                "Governor: {} (Core Min: {:.0} MHz, Core Max: {:.0} MHz)",
+                self.governor, low, peak
+            );
+            if !self.energy_performance_preference.is_empty() {
+                policy.push_str(&format!(", EPP: {}", self.energy_performance_preference));
+            }
+            mouse.add(policy);
+        }
+
         // Add temperature information if available
         if self.tctl_temp > 0.0 {
             mouse.add(format!(
@@ -279,11 +487,40 @@ impl CpuStats {
             ));
         }
 
-        if self.tccd1_temp > 0.0 {
+        if let [ccd] = self.ccd_temps.as_slice() {
+            mouse.add(format!(
+                "Tccd{}: {:.1}°C (Min: {:.1}°C, Max: {:.1}°C)",
+                ccd.ccd, ccd.temp, ccd.min_temp, ccd.max_temp
+            ));
+        } else if !self.ccd_temps.is_empty() {
+            // Several CCDs: one compact line of current temps, one of min/max pairs
+            let temps: Vec<String> = self
+                .ccd_temps
+                .iter()
+                .map(|ccd| format!("{}: {:.1}", ccd.ccd, ccd.temp))
+                .collect();
+            let hottest = self
+                .ccd_temps
+                .iter()
+                .map(|ccd| ccd.temp)
+                .fold(0.0, f32::max);
+            let coolest = self
+                .ccd_temps
+                .iter()
+                .map(|ccd| ccd.temp)
+                .fold(f32::MAX, f32::min);
             mouse.add(format!(
-                "Tccd1: {:.1}°C (Min: {:.1}°C, Max: {:.1}°C)",
-                self.tccd1_temp, self.min_tccd1_temp, self.max_tccd1_temp
+                "Tccd: {}°C (Spread: {:.1}°C)",
+                temps.join(", "),
+                hottest - coolest
             ));
+
+            let min_max: Vec<String> = self
+                .ccd_temps
+                .iter()
+                .map(|ccd| format!("{}: {:.1}/{:.1}", ccd.ccd, ccd.min_temp, ccd.max_temp))
+                .collect();
+            mouse.add(format!("Tccd Min/Max: {}°C", min_max.join(", ")));
         }
 
         if self.composite_temp > 0.0 && self.composite_temp != self.tctl_temp {
@@ -293,44 +530,102 @@ impl CpuStats {
             ));
         }
 
+        if let Some(index) = self.hottest_core {
+            if let Some(temp) = self.core_temps[index] {
+                let mut spans = vec![
+                    Span::raw(format!("Hottest Core: C{} ", index)),
+                    Span::styled(format!("{:.1}°C", temp.temp), temp.style()),
+                    Span::raw(format!(
+                        " (Min: {:.1}°C, Max: {:.1}°C",
+                        self.min_hottest_temp, self.max_hottest_temp
+                    )),
+                ];
+                if let Some(crit) = temp.crit {
+                    spans.push(Span::raw(format!(", Crit: {:.1}°C", crit)));
+                }
+                spans.push(Span::raw(")"));
+                mouse.add_line(Line::from(spans));
+            }
+        }
+
+        // Add power draw per domain if available
+        self.power.add_to_mouse(&mut mouse);
+
         // Add per-core usage information (limited to first 16 cores to keep UI manageable)
         if !self.core_usage.is_empty() {
             mouse.add(String::from("")); // Empty line to separate
             mouse.add(String::from("Per-core Usage:"));
 
-            // Display cores in groups of 4 to save vertical space
-            let mut core_groups = Vec::new();
-            for chunk in self.core_usage.chunks(4) {
-                let mut group_str = String::new();
-                for (i, usage) in chunk.iter().enumerate() {
-                    let core_idx = core_groups.len() * 4 + i;
-                    if !group_str.is_empty() {
-                        group_str.push_str(", ");
+            let freq_of = |cpu: usize| {
+                self.core_freq
+                    .get(&cpu)
+                    .map(|freq| format!(" {:.0}MHz", freq.current))
+                    .unwrap_or_default()
+            };
+
+            // One entry per physical core with its hyperthread siblings together,
+            // or per logical CPU when the topology is unknown
+            let entries: Vec<Vec<Span<'static>>> = if self.topology.is_empty() {
+                self.core_usage
+                    .iter()
+                    .map(|(cpu, usage)| {
+                        vec![Span::raw(format!(
+                            "CPU{}: {:.1}%{}",
+                            cpu,
+                            usage,
+                            freq_of(*cpu)
+                        ))]
+                    })
+                    .collect()
+            } else {
+                self.topology
+                    .cores
+                    .iter()
+                    .enumerate()
+                    .map(|(i, core)| {
+                        let usages: Vec<String> = core
+                            .threads
+                            .iter()
+                            .map(|thread| {
+                                format!(
+                                    "{:.1}%",
+                                    self.core_usage.get(thread).copied().unwrap_or_default()
+                                )
+                            })
+                            .collect();
+                        // Siblings share a clock, so one frequency per core is enough
+                        let mut entry = vec![Span::raw(format!(
+                            "C{}: {}{}",
+                            i,
+                            usages.join("/"),
+                            freq_of(core.threads[0])
+                        ))];
+                        if let Some(Some(temp)) = self.core_temps.get(i) {
+                            entry.push(Span::styled(format!(" {:.0}°C", temp.temp), temp.style()));
+                        }
+                        entry
+                    })
+                    .collect()
+            };
+
+            // Display cores in groups of 4 to save vertical space (max 4 lines for 16 cores)
+            for chunk in entries.chunks(4).take(4) {
+                let mut spans = Vec::new();
+                for entry in chunk {
+                    if !spans.is_empty() {
+                        spans.push(Span::raw(", "));
                     }
-                    group_str.push_str(&format!("CPU{}: {:.1}%", core_idx, usage));
+                    spans.extend(entry.iter().cloned());
                 }
-                core_groups.push(group_str);
-            }
-
-            // Add each group as a line (max 4 lines for 16 cores)
-            for (i, group) in core_groups.iter().take(4).enumerate() {
-                mouse.add(group.clone());
+                mouse.add_line(Line::from(spans));
             }
 
             // If there are more cores, indicate that
-            if self.core_usage.len() > 16 {
-                mouse.add(format!("... and {} more cores", self.core_usage.len() - 16));
+            if entries.len() > 16 {
+                mouse.add(format!("... and {} more cores", entries.len() - 16));
             }
         }
 
-        return mouse;
+        mouse
     }
 }
-
-// Helper function to read a file into a string (reused from gpu.rs)
-fn read_file_to_string(path: &Path) -> io::Result<String> {
-    let mut file = File::open(path)?;
-    let mut contents = String::new();
-    file.read_to_string(&mut contents)?;
-    Ok(contents)
-}
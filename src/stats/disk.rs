@@ -0,0 +1,319 @@
+use crate::{
+    common::{format_rate, read_file_to_string, SysWrapper},
+    renderer::core::Mouse,
+};
+use byte_unit::{AdjustedByte, Byte, UnitType};
+use std::ffi::CString;
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+// /proc/diskstats always counts in 512 byte sectors regardless of the device
+const SECTOR_SIZE: u64 = 512;
+// Usage changes slowly and statvfs can block on a slow mount, so list and query the
+// filesystems less often than the I/O counters
+const FILESYSTEM_INTERVAL: Duration = Duration::from_secs(5);
+// statvfs on these hangs the UI while the server is unreachable (hard NFS mounts, stale
+// SMB sessions, FUSE network mounts)
+const NETWORK_FILESYSTEMS: [&str; 10] = [
+    "nfs",
+    "nfs4",
+    "cifs",
+    "smb3",
+    "smbfs",
+    "9p",
+    "ceph",
+    "glusterfs",
+    "afs",
+    "fuse.sshfs",
+];
+
+#[derive(Clone)]
+pub struct DiskStats {
+    devices: Vec<BlockDeviceInfo>,
+    filesystems: Vec<FilesystemInfo>,
+    last_update: Option<Instant>,
+    filesystems_refreshed: Option<Instant>,
+}
+
+#[derive(Clone, Copy)]
+struct DiskCounters {
+    reads_completed: u64,
+    sectors_read: u64,
+    writes_completed: u64,
+    sectors_written: u64,
+}
+
+#[derive(Clone)]
+pub struct BlockDeviceInfo {
+    pub name: String,
+    counters: DiskCounters,
+    pub read_bytes_per_sec: f64,
+    pub write_bytes_per_sec: f64,
+    pub read_iops: f64,
+    pub write_iops: f64,
+    pub peak_read_bytes_per_sec: f64,
+    pub peak_write_bytes_per_sec: f64,
+}
+
+#[derive(Clone)]
+pub struct FilesystemInfo {
+    pub mount_point: String,
+    pub device: String,
+    pub file_system: String,
+    pub total: AdjustedByte,
+    pub used: AdjustedByte,
+    pub percentage_used: f64,
+    pub min_used: AdjustedByte,
+    pub max_used: AdjustedByte,
+    // (used, total) inodes; filesystems like btrfs have no fixed inode table
+    pub inodes: Option<(u64, u64)>,
+}
+
+impl DiskStats {
+    pub fn new() -> Self {
+        DiskStats {
+            devices: Vec::new(),
+            filesystems: Vec::new(),
+            last_update: None,
+            filesystems_refreshed: None,
+        }
+    }
+
+    pub fn update(&mut self, sys_obj: &mut SysWrapper) {
+        self.update_devices();
+        self.update_filesystems(sys_obj);
+    }
+
+    fn update_devices(&mut self) {
+        let contents = match read_file_to_string(Path::new("/proc/diskstats")) {
+            Ok(contents) => contents,
+            Err(_) => return,
+        };
+
+        let now = Instant::now();
+        let elapsed = self
+            .last_update
+            .map(|last| now.duration_since(last).as_secs_f64())
+            .unwrap_or(0.0);
+
+        let mut devices = Vec::new();
+        for (name, counters) in parse_proc_diskstats(&contents) {
+            // Only whole disks live directly under /sys/block, partitions do not
+            if !Path::new("/sys/block").join(&name).exists()
+                || name.starts_with("loop")
+                || name.starts_with("ram")
+            {
+                continue;
+            }
+
+            let mut info = BlockDeviceInfo {
+                name,
+                counters,
+                read_bytes_per_sec: 0.0,
+                write_bytes_per_sec: 0.0,
+                read_iops: 0.0,
+                write_iops: 0.0,
+                peak_read_bytes_per_sec: 0.0,
+                peak_write_bytes_per_sec: 0.0,
+            };
+
+            if let Some(previous) = self.devices.iter().find(|d| d.name == info.name) {
+                if elapsed > 0.0 {
+                    let rate = |now: u64, before: u64| now.saturating_sub(before) as f64 / elapsed;
+                    info.read_bytes_per_sec =
+                        rate(counters.sectors_read, previous.counters.sectors_read)
+                            * SECTOR_SIZE as f64;
+                    info.write_bytes_per_sec =
+                        rate(counters.sectors_written, previous.counters.sectors_written)
+                            * SECTOR_SIZE as f64;
+                    info.read_iops =
+                        rate(counters.reads_completed, previous.counters.reads_completed);
+                    info.write_iops = rate(
+                        counters.writes_completed,
+                        previous.counters.writes_completed,
+                    );
+                }
+                info.peak_read_bytes_per_sec = previous
+                    .peak_read_bytes_per_sec
+                    .max(info.read_bytes_per_sec);
+                info.peak_write_bytes_per_sec = previous
+                    .peak_write_bytes_per_sec
+                    .max(info.write_bytes_per_sec);
+            }
+
+            devices.push(info);
+        }
+
+        self.devices = devices;
+        self.last_update = Some(now);
+    }
+
+    fn update_filesystems(&mut self, sys_obj: &mut SysWrapper) {
+        if self
+            .filesystems_refreshed
+            .is_some_and(|last| last.elapsed() < FILESYSTEM_INTERVAL)
+        {
+            return;
+        }
+        self.filesystems_refreshed = Some(Instant::now());
+
+        // Re-list so mounts that come and go are picked up
+        sys_obj.disks.refresh(true);
+
+        let mut filesystems: Vec<FilesystemInfo> = Vec::new();
+        for disk in sys_obj.disks.list() {
+            let mount_point = disk.mount_point().to_string_lossy().to_string();
+            if filesystems.iter().any(|fs| fs.mount_point == mount_point) {
+                continue;
+            }
+            let file_system = disk.file_system().to_string_lossy().to_string();
+            if NETWORK_FILESYSTEMS.contains(&file_system.as_str()) {
+                continue;
+            }
+
+            let total_bytes = disk.total_space();
+            let used_bytes = total_bytes.saturating_sub(disk.available_space());
+            let used = Byte::from_u64(used_bytes).get_appropriate_unit(UnitType::Binary);
+            let percentage_used = if total_bytes > 0 {
+                (used_bytes as f64 / total_bytes as f64) * 100.0
+            } else {
+                0.0
+            };
+
+            let (min_used, max_used) = match self
+                .filesystems
+                .iter()
+                .find(|fs| fs.mount_point == mount_point)
+            {
+                Some(previous) => (previous.min_used.min(used), previous.max_used.max(used)),
+                None => (used, used),
+            };
+
+            filesystems.push(FilesystemInfo {
+                inodes: inode_usage(disk.mount_point()),
+                mount_point,
+                device: disk.name().to_string_lossy().to_string(),
+                file_system,
+                total: Byte::from_u64(total_bytes).get_appropriate_unit(UnitType::Binary),
+                used,
+                percentage_used,
+                min_used,
+                max_used,
+            });
+        }
+
+        self.filesystems = filesystems;
+    }
+
+    pub fn get_mouse(&self) -> Mouse {
+        let title = String::from("Disks");
+        let mut mouse = Mouse::new(title);
+
+        if self.devices.is_empty() && self.filesystems.is_empty() {
+            mouse.add(String::from("No disks detected"));
+            return mouse;
+        }
+
+        if !self.devices.is_empty() {
+            mouse.add(String::from("I/O:"));
+            for device in &self.devices {
+                mouse.add(format!(
+                    "  {}: R {} ({:.0} IOPS) W {} ({:.0} IOPS)",
+                    device.name,
+                    format_rate(device.read_bytes_per_sec),
+                    device.read_iops,
+                    format_rate(device.write_bytes_per_sec),
+                    device.write_iops
+                ));
+                mouse.add(format!(
+                    "    Peak: R {}, W {}",
+                    format_rate(device.peak_read_bytes_per_sec),
+                    format_rate(device.peak_write_bytes_per_sec)
+                ));
+            }
+        }
+
+        if !self.filesystems.is_empty() {
+            if !self.devices.is_empty() {
+                mouse.add(String::from("")); // Empty line to separate
+            }
+            mouse.add(String::from("Filesystems:"));
+            for fs in &self.filesystems {
+                mouse.add(format!(
+                    "  {} ({}, {})",
+                    fs.mount_point, fs.device, fs.file_system
+                ));
+                mouse.add(format!(
+                    "    Used: {:.2}{}/{:.2}{} ({:.1}%)",
+                    fs.used.get_value(),
+                    fs.used.get_unit(),
+                    fs.total.get_value(),
+                    fs.total.get_unit(),
+                    fs.percentage_used
+                ));
+                mouse.add(format!(
+                    "    Max: {:.2}{}, Min: {:.2}{}",
+                    fs.max_used.get_value(),
+                    fs.max_used.get_unit(),
+                    fs.min_used.get_value(),
+                    fs.min_used.get_unit()
+                ));
+                if let Some((used, total)) = fs.inodes {
+                    mouse.add(format!(
+                        "    Inodes: {}/{} ({:.1}%)",
+                        used,
+                        total,
+                        (used as f64 / total as f64) * 100.0
+                    ));
+                }
+            }
+        }
+
+        mouse
+    }
+}
+
+// Parse /proc/diskstats. Each line looks like:
+// "major minor name reads merged sectors_read ms_reading writes merged sectors_written ..."
+fn parse_proc_diskstats(contents: &str) -> Vec<(String, DiskCounters)> {
+    let mut devices = Vec::new();
+
+    for line in contents.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 10 {
+            continue;
+        }
+
+        let field = |i: usize| fields[i].parse::<u64>().unwrap_or_default();
+        devices.push((
+            fields[2].to_string(),
+            DiskCounters {
+                reads_completed: field(3),
+                sectors_read: field(5),
+                writes_completed: field(7),
+                sectors_written: field(9),
+            },
+        ));
+    }
+
+    devices
+}
+
+// Inode usage via statvfs, which sysinfo does not expose
+fn inode_usage(mount_point: &Path) -> Option<(u64, u64)> {
+    let path = CString::new(mount_point.as_os_str().as_bytes()).ok()?;
+    // SAFETY: statvfs is plain old data, so an all-zero value is valid
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: path is NUL-terminated and stat points to a properly sized statvfs
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+
+    let total = stat.f_files as u64;
+    if total == 0 {
+        return None;
+    }
+    let used = total.saturating_sub(stat.f_ffree as u64);
+    Some((used, total))
+}
@@ -0,0 +1,208 @@
+use crate::common::read_file_to_string;
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Instant;
+
+// DRM clients as reported in /proc/<pid>/fdinfo, see the kernel's drm-usage-stats docs
+#[derive(Clone)]
+pub struct DrmClient {
+    pub pid: u32,
+    pub client_id: u64,
+    // PCI address of the device, e.g. "0000:00:02.0"
+    pub pdev: String,
+    // Busy time in ns per engine class (drm-engine-<class>, i915 and amdgpu)
+    pub engine_ns: HashMap<String, u64>,
+    // Busy and total GPU cycles per engine class (drm-cycles-/drm-total-cycles-, xe)
+    pub engine_cycles: HashMap<String, (u64, u64)>,
+    // Device-local memory in bytes (vram* on amdgpu/xe, local* on discrete i915); GTT and
+    // system memory are left out
+    pub vram: u64,
+}
+
+// Turns the cumulative engine counters into busy percentages between two scans
+#[derive(Clone)]
+pub struct EngineSampler {
+    // (pdev, client id, engine class) -> (busy, total) at the previous scan
+    previous: HashMap<(String, u64, String), (u64, u64)>,
+    last_sample: Option<Instant>,
+}
+
+impl EngineSampler {
+    pub fn new() -> Self {
+        EngineSampler {
+            previous: HashMap::new(),
+            last_sample: None,
+        }
+    }
+
+    // Busy percent per (pdev, client id, engine class); clients seen for the first time
+    // are left out until the next scan
+    pub fn sample(&mut self, clients: &[DrmClient]) -> HashMap<(String, u64, String), f64> {
+        let now = Instant::now();
+        let elapsed_ns = self
+            .last_sample
+            .map(|last| now.duration_since(last).as_nanos() as u64)
+            .unwrap_or_default();
+        self.last_sample = Some(now);
+
+        let mut current = HashMap::new();
+        for client in clients {
+            // ns counters run against wall-clock time
+            for (class, busy) in &client.engine_ns {
+                let key = (client.pdev.clone(), client.client_id, class.clone());
+                current.insert(key, (*busy, 0));
+            }
+            for (class, cycles) in &client.engine_cycles {
+                let key = (client.pdev.clone(), client.client_id, class.clone());
+                current.insert(key, *cycles);
+            }
+        }
+
+        let mut busy_percent = HashMap::new();
+        for (key, (busy, total)) in &current {
+            let Some((previous_busy, previous_total)) = self.previous.get(key) else {
+                continue;
+            };
+            let delta_total = if *total > 0 {
+                total.saturating_sub(*previous_total)
+            } else {
+                elapsed_ns
+            };
+            if delta_total == 0 {
+                continue;
+            }
+            let delta_busy = busy.saturating_sub(*previous_busy);
+            let percent = (delta_busy as f64 / delta_total as f64 * 100.0).min(100.0);
+            busy_percent.insert(key.clone(), percent);
+        }
+
+        self.previous = current;
+        busy_percent
+    }
+}
+
+// Scan every process we are allowed to inspect for open DRM file descriptors
+pub fn scan_drm_clients() -> Vec<DrmClient> {
+    let Ok(procs) = std::fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+
+    let mut clients: Vec<DrmClient> = Vec::new();
+    for proc_entry in procs.filter_map(Result::ok) {
+        let Ok(pid) = proc_entry.file_name().to_string_lossy().parse::<u32>() else {
+            continue;
+        };
+        // Fails with EACCES for other users' processes unless running as root
+        let Ok(fds) = std::fs::read_dir(proc_entry.path().join("fd")) else {
+            continue;
+        };
+
+        for fd in fds.filter_map(Result::ok) {
+            // Only open the fdinfo of fds that point at a DRM node; reading every fdinfo
+            // file on a busy machine is tens of thousands of reads per tick
+            let Ok(target) = std::fs::read_link(fd.path()) else {
+                continue;
+            };
+            if !target.starts_with("/dev/dri/") {
+                continue;
+            }
+            let fdinfo = proc_entry.path().join("fdinfo").join(fd.file_name());
+            let Some(client) = parse_fdinfo(&fdinfo, pid) else {
+                continue;
+            };
+            // A client shows up once per fd that refers to it (dup, fork)
+            if !clients
+                .iter()
+                .any(|c| c.pdev == client.pdev && c.client_id == client.client_id)
+            {
+                clients.push(client);
+            }
+        }
+    }
+    clients
+}
+
+fn parse_fdinfo(path: &Path, pid: u32) -> Option<DrmClient> {
+    let contents = read_file_to_string(path).ok()?;
+    // Kernels without DRM usage stats print no drm-* keys at all
+    if !contents.contains("drm-client-id") {
+        return None;
+    }
+
+    let mut client = DrmClient {
+        pid,
+        client_id: 0,
+        pdev: String::new(),
+        engine_ns: HashMap::new(),
+        engine_cycles: HashMap::new(),
+        vram: 0,
+    };
+    let mut total_cycles: HashMap<String, u64> = HashMap::new();
+    let (mut total_vram, mut legacy_vram) = (0, 0);
+
+    for line in contents.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+
+        if key == "drm-client-id" {
+            client.client_id = value.parse().ok()?;
+        } else if key == "drm-pdev" {
+            client.pdev = value.to_string();
+        } else if let Some(class) = key.strip_prefix("drm-engine-") {
+            // drm-engine-capacity-<class> is the engine count, not a counter
+            if !class.starts_with("capacity-") {
+                client
+                    .engine_ns
+                    .insert(class.to_string(), parse_leading_number(value));
+            }
+        } else if let Some(class) = key.strip_prefix("drm-total-cycles-") {
+            total_cycles.insert(class.to_string(), parse_leading_number(value));
+        } else if let Some(class) = key.strip_prefix("drm-cycles-") {
+            client
+                .engine_cycles
+                .insert(class.to_string(), (parse_leading_number(value), 0));
+        } else if let Some(region) = key.strip_prefix("drm-total-") {
+            if region.starts_with("vram") || region.starts_with("local") {
+                total_vram += parse_memory(value);
+            }
+        } else if key == "drm-memory-vram" {
+            legacy_vram += parse_memory(value);
+        }
+    }
+
+    // Older amdgpu kernels only have the legacy drm-memory-<region> keys
+    client.vram = if total_vram > 0 {
+        total_vram
+    } else {
+        legacy_vram
+    };
+
+    for (class, cycles) in client.engine_cycles.iter_mut() {
+        cycles.1 = total_cycles.get(class).copied().unwrap_or_default();
+    }
+    // Cycle counters without a total cannot be turned into a percentage
+    client.engine_cycles.retain(|_, (_, total)| *total > 0);
+
+    Some(client)
+}
+
+fn parse_leading_number(value: &str) -> u64 {
+    value
+        .split_whitespace()
+        .next()
+        .and_then(|number| number.parse().ok())
+        .unwrap_or_default()
+}
+
+// Memory values look like "1024 KiB"; the unit is optional
+fn parse_memory(value: &str) -> u64 {
+    let number = parse_leading_number(value);
+    match value.split_whitespace().nth(1) {
+        Some("KiB") => number * 1024,
+        Some("MiB") => number * 1024 * 1024,
+        Some("GiB") => number * 1024 * 1024 * 1024,
+        _ => number,
+    }
+}
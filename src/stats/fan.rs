@@ -0,0 +1,148 @@
+use crate::{
+    common::{read_file_to_string, SysWrapper},
+    renderer::core::Mouse,
+    stats::{
+        gpu::{GpuInfo, GpuVendor},
+        hwmon::SensorKind,
+    },
+};
+
+#[derive(Clone)]
+pub struct FanStats {
+    fans: Vec<FanInfo>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum FanUnit {
+    Rpm,
+    // nvidia-smi only reports the target duty cycle, not a tachometer reading
+    Percent,
+}
+
+#[derive(Clone)]
+pub struct FanInfo {
+    id: String,
+    pub source: String,
+    pub label: String,
+    pub speed: f64,
+    pub unit: FanUnit,
+    // Duty cycle of the matching pwmN output, if the chip has one
+    pub pwm_percent: Option<f64>,
+    pub min_speed: f64,
+    pub max_speed: f64,
+}
+
+impl FanInfo {
+    // A fan that is being driven but does not report any rotation
+    pub fn is_stalled(&self) -> bool {
+        self.unit == FanUnit::Rpm && self.speed == 0.0 && self.pwm_percent.unwrap_or(0.0) > 0.0
+    }
+
+    fn format_speed(&self, speed: f64) -> String {
+        match self.unit {
+            FanUnit::Rpm => format!("{:.0} RPM", speed),
+            FanUnit::Percent => format!("{:.0}%", speed),
+        }
+    }
+}
+
+impl FanStats {
+    pub fn new() -> Self {
+        FanStats { fans: Vec::new() }
+    }
+
+    pub fn update(&mut self, sys_obj: &mut SysWrapper, gpus: &[GpuInfo]) {
+        let mut fans = Vec::new();
+
+        // Motherboard, AMD GPU and any other chip fans come from hwmon
+        for chip in sys_obj.hwmon.chips() {
+            for sensor in chip.sensors_of(SensorKind::Fan) {
+                let pwm_path = chip.path.join(format!("pwm{}", sensor.index));
+                let pwm_percent = read_file_to_string(&pwm_path)
+                    .ok()
+                    .and_then(|pwm| pwm.trim().parse::<f64>().ok())
+                    .map(|pwm| pwm / 255.0 * 100.0);
+
+                fans.push(FanInfo {
+                    id: format!("{}/fan{}", chip.id(), sensor.index),
+                    source: chip.display_name(),
+                    label: sensor.label.clone(),
+                    speed: sensor.value,
+                    unit: FanUnit::Rpm,
+                    pwm_percent,
+                    min_speed: sensor.value,
+                    max_speed: sensor.value,
+                });
+            }
+        }
+
+        // NVIDIA fans are reported by nvidia-smi as part of the GPU query
+        for gpu in gpus {
+            // AMD GPU fans are already covered by their hwmon chip above
+            if gpu.vendor != GpuVendor::Nvidia {
+                continue;
+            }
+            if let Some(speed) = gpu.fan_speed_percent {
+                fans.push(FanInfo {
+                    id: format!("gpu {}", gpu.id()),
+                    source: gpu.name.clone(),
+                    label: String::from("Fan"),
+                    speed,
+                    unit: FanUnit::Percent,
+                    pwm_percent: None,
+                    min_speed: speed,
+                    max_speed: speed,
+                });
+            }
+        }
+
+        // Carry min/max over from the previous tick
+        for fan in &mut fans {
+            if let Some(previous) = self.fans.iter().find(|previous| previous.id == fan.id) {
+                fan.min_speed = previous.min_speed.min(fan.speed);
+                fan.max_speed = previous.max_speed.max(fan.speed);
+            }
+        }
+
+        self.fans = fans;
+    }
+
+    pub fn get_mouse(&self) -> Mouse {
+        let title = String::from("Fans");
+        let mut mouse = Mouse::new(title);
+
+        if self.fans.is_empty() {
+            mouse.add(String::from("No fans detected"));
+            return mouse;
+        }
+
+        let mut current_source = "";
+        for fan in &self.fans {
+            // Group fans under the chip or GPU they belong to
+            if fan.source != current_source {
+                if !current_source.is_empty() {
+                    mouse.add(String::from("")); // Add empty line between sources
+                }
+                mouse.add(fan.source.clone());
+                current_source = &fan.source;
+            }
+
+            let mut line = format!(
+                "  {}: {} (Min: {}, Max: {})",
+                fan.label,
+                fan.format_speed(fan.speed),
+                fan.format_speed(fan.min_speed),
+                fan.format_speed(fan.max_speed)
+            );
+            if let Some(pwm) = fan.pwm_percent {
+                line.push_str(&format!(" PWM: {:.0}%", pwm));
+            }
+            if fan.is_stalled() {
+                line.push_str(" [STALLED]");
+            }
+            mouse.add(line);
+        }
+
+        mouse
+    }
+}
@@ -1,18 +1,51 @@
 use crate::{
-    common::{get_default_byte, SysWrapper},
+    common::{get_default_byte, read_file_to_string, SysWrapper},
     renderer::core::Mouse,
+    stats::{
+        drm_fdinfo::{scan_drm_clients, DrmClient, EngineSampler},
+        nvml::{NvidiaBackend, Nvml},
+    },
 };
 use byte_unit::{AdjustedByte, Byte, UnitType};
-use std::fs::File;
-use std::io::{self, Read};
+use std::collections::HashMap;
+use std::io;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::{Duration, Instant};
+
+// Processes listed under each GPU
+const GPU_PROCESS_LIMIT: usize = 5;
+// nvidia-smi is slow to fork, and compute apps come and go rarely; NVML is asked every tick
+const NVIDIA_PROCESS_INTERVAL: Duration = Duration::from_secs(2);
+// Walking every process's fds is costly, so DRM clients are rescanned at this pace and
+// Intel busy percent and the DRM process lists update with it
+const DRM_CLIENT_INTERVAL: Duration = Duration::from_secs(2);
 
-#[derive(Clone)]
 pub struct GpuStats {
     gpus: Vec<GpuInfo>,
+    // NVML when libnvidia-ml is installed, nvidia-smi otherwise
+    nvidia: Box<dyn NvidiaBackend>,
+    // AMD and Intel cards from sysfs and their clients from /proc
+    drm: Box<dyn DrmBackend>,
     // Track previous stats for min/max values
     previous_gpu_states: Vec<GpuMinMaxStats>,
+    // Engine busy time from DRM fdinfo, turned into percentages between scans
+    engine_sampler: EngineSampler,
+    // Last DRM client scan and the busy percent per (pdev, client id, engine class)
+    drm_clients: Vec<DrmClient>,
+    engine_busy: HashMap<(String, u64, String), f64>,
+    drm_clients_scanned: Option<Instant>,
+    // Previous RC6 and energy counters per Intel card, keyed by PCI bus id
+    intel_samples: HashMap<String, IntelSample>,
+    // Marketing names from lspci per PCI bus id; None when lspci had no answer
+    lspci_names: HashMap<String, Option<String>>,
+}
+
+#[derive(Clone)]
+struct IntelSample {
+    time: Instant,
+    rc6_residency_ms: Option<f64>,
+    energy_uj: Option<f64>,
 }
 
 #[derive(Clone)]
@@ -34,21 +67,193 @@ struct GpuMinMaxStats {
 pub struct GpuInfo {
     pub name: String,
     pub vendor: GpuVendor,
+    // PCI address without the zero domain, e.g. "03:00.0"; empty if unknown
+    pub bus_id: String,
     // Temperatures
     pub edge_temp: f64,
-    pub junction_temp: f64,
-    pub memory_temp: f64,
+    pub junction_temp: Option<f64>,
+    pub memory_temp: Option<f64>,
     // Usage
     pub usage_percent: f64,
     pub memory_total: AdjustedByte,
     pub memory_used: AdjustedByte,
     pub memory_percent: f64,
+    // Fan duty in percent (nvidia-smi fan.speed, amdgpu pwm1) and speed where reported
+    pub fan_speed_percent: Option<f64>,
+    pub fan_rpm: Option<f64>,
+    // Graphics core voltage in V (amdgpu vddgfx)
+    pub core_voltage: Option<f64>,
+    // Board power in W
+    pub power_draw: Option<f64>,
+    pub power_limit: Option<f64>,
+    // Current and maximum clocks in MHz
+    pub core_clock: Option<f64>,
+    // Frequency the driver asked for, which the hardware may not reach (Intel)
+    pub requested_core_clock: Option<f64>,
+    pub memory_clock: Option<f64>,
+    pub sm_clock: Option<f64>,
+    pub max_core_clock: Option<f64>,
+    pub max_memory_clock: Option<f64>,
+    pub max_sm_clock: Option<f64>,
+    // Performance state, e.g. "P0"
+    pub pstate: Option<String>,
+    pub pcie_gen: Option<u32>,
+    pub pcie_width: Option<u32>,
+    // Memory controller, video encoder and decoder load in percent
+    pub memory_util_percent: Option<f64>,
+    pub encoder_percent: Option<f64>,
+    pub decoder_percent: Option<f64>,
+    // Names of the active clock throttle reasons
+    pub throttle_reasons: Vec<String>,
+    // Current amdgpu DPM levels for the core and memory clocks
+    pub sclk_dpm: Option<DpmState>,
+    pub mclk_dpm: Option<DpmState>,
+    // GTT (system memory mapped for the GPU) and CPU-visible VRAM usage
+    pub gtt_used: Option<AdjustedByte>,
+    pub gtt_total: Option<AdjustedByte>,
+    pub vis_vram_used: Option<AdjustedByte>,
+    pub vis_vram_total: Option<AdjustedByte>,
+    // Share of time the GT spent in RC6 power gating (Intel)
+    pub rc6_percent: Option<f64>,
+    // Busy percent per engine class from DRM fdinfo (Intel)
+    pub engine_busy: Vec<(String, f64)>,
+    // Top consumers of this GPU, busiest first
+    pub processes: Vec<GpuProcess>,
+}
+
+#[derive(Clone)]
+pub struct GpuProcess {
+    pub pid: u32,
+    pub name: String,
+    // Busiest engine class; nvidia-smi only reports memory
+    pub busy_percent: Option<f64>,
+    // VRAM held by the process on this card
+    pub memory: Option<AdjustedByte>,
+}
+
+#[derive(Clone)]
+pub struct DpmState {
+    // Index of the active level and the number of levels
+    pub level: u32,
+    pub levels: u32,
+}
+
+impl GpuInfo {
+    // Everything the backend could not read stays at zero or absent
+    pub fn new(bus_id: String, name: String, vendor: GpuVendor) -> Self {
+        let default_byte = *get_default_byte();
+        GpuInfo {
+            name,
+            vendor,
+            bus_id,
+            edge_temp: 0.0,
+            junction_temp: None,
+            memory_temp: None,
+            usage_percent: 0.0,
+            memory_total: default_byte,
+            memory_used: default_byte,
+            memory_percent: 0.0,
+            fan_speed_percent: None,
+            fan_rpm: None,
+            core_voltage: None,
+            power_draw: None,
+            power_limit: None,
+            core_clock: None,
+            requested_core_clock: None,
+            memory_clock: None,
+            sm_clock: None,
+            max_core_clock: None,
+            max_memory_clock: None,
+            max_sm_clock: None,
+            pstate: None,
+            pcie_gen: None,
+            pcie_width: None,
+            memory_util_percent: None,
+            encoder_percent: None,
+            decoder_percent: None,
+            throttle_reasons: Vec::new(),
+            sclk_dpm: None,
+            mclk_dpm: None,
+            gtt_used: None,
+            gtt_total: None,
+            vis_vram_used: None,
+            vis_vram_total: None,
+            rc6_percent: None,
+            engine_busy: Vec::new(),
+            processes: Vec::new(),
+        }
+    }
+
+    // Key for the min/max history; the bus address tells identical cards apart and stays
+    // the same when a card is unplugged and plugged back into the same slot, while the
+    // name keeps a different card in that slot from inheriting the old one's history
+    pub fn id(&self) -> String {
+        if self.bus_id.is_empty() {
+            format!("{} {}", self.vendor.as_string(), &self.name)
+        } else {
+            format!("{} {}", self.bus_id, &self.name)
+        }
+    }
 }
 
+// Fields requested from nvidia-smi; parse_nvidia_line looks values up by field name
+const NVIDIA_QUERY_FIELDS: [&str; 23] = [
+    "name",
+    "temperature.gpu",
+    "utilization.gpu",
+    "memory.used",
+    "memory.total",
+    "fan.speed",
+    "power.draw",
+    "power.limit",
+    "clocks.gr",
+    "clocks.mem",
+    "clocks.sm",
+    "clocks.max.gr",
+    "clocks.max.mem",
+    "clocks.max.sm",
+    "pstate",
+    "pcie.link.gen.current",
+    "pcie.link.width.current",
+    "temperature.memory",
+    "utilization.memory",
+    "utilization.encoder",
+    "utilization.decoder",
+    "clocks_throttle_reasons.active",
+    "pci.bus_id",
+];
+
+// nvidia-smi fails the whole query if it does not know one field (e.g. temperature.memory
+// on older drivers), so fall back to fields every driver supports
+const NVIDIA_BASE_FIELDS: [&str; 7] = [
+    "name",
+    "temperature.gpu",
+    "utilization.gpu",
+    "memory.used",
+    "memory.total",
+    "fan.speed",
+    "pci.bus_id",
+];
+
+// Bits of clocks_throttle_reasons.active, see the nvidia-smi documentation
+const NVIDIA_THROTTLE_REASONS: [(u64, &str); 9] = [
+    (0x1, "Idle"),
+    (0x2, "App Clocks"),
+    (0x4, "SW Power Cap"),
+    (0x8, "HW Slowdown"),
+    (0x10, "Sync Boost"),
+    (0x20, "SW Thermal"),
+    (0x40, "HW Thermal"),
+    (0x80, "HW Power Brake"),
+    (0x100, "Display Clocks"),
+];
+
 #[derive(Clone, PartialEq)]
 pub enum GpuVendor {
     Nvidia,
     Amd,
+    Intel,
+    #[allow(dead_code)]
     Unknown,
 }
 
@@ -57,6 +262,7 @@ impl GpuVendor {
         match self {
             GpuVendor::Nvidia => "NVIDIA",
             GpuVendor::Amd => "AMD",
+            GpuVendor::Intel => "Intel",
             GpuVendor::Unknown => "Unknown",
         }
     }
@@ -64,26 +270,88 @@ impl GpuVendor {
 
 impl GpuStats {
     pub fn new() -> Self {
+        let nvidia: Box<dyn NvidiaBackend> = match Nvml::load() {
+            Some(nvml) => Box::new(nvml),
+            None => Box::new(NvidiaSmi::new()),
+        };
+        GpuStats::with_backends(nvidia, Box::new(SysfsDrm))
+    }
+
+    // Use the given sources for NVIDIA and DRM readings instead of probing the system
+    pub fn with_backends(nvidia: Box<dyn NvidiaBackend>, drm: Box<dyn DrmBackend>) -> Self {
         GpuStats {
             gpus: Vec::new(),
+            nvidia,
+            drm,
             previous_gpu_states: Vec::new(),
+            engine_sampler: EngineSampler::new(),
+            drm_clients: Vec::new(),
+            engine_busy: HashMap::new(),
+            drm_clients_scanned: None,
+            intel_samples: HashMap::new(),
+            lspci_names: HashMap::new(),
         }
     }
 
-    pub fn update(&mut self, sys_obj: &mut SysWrapper) {
+    pub fn update(&mut self, _sys_obj: &mut SysWrapper) {
         // Clear existing GPU info
-        let previous_gpus = self.gpus.clone();
         self.gpus.clear();
 
         // Detect and update NVIDIA GPUs
-        self.update_nvidia_gpus();
+        self.gpus.extend(self.nvidia.query());
+
+        // DRM clients give Intel its busy percent and every DRM driver its process list.
+        // Walking /proc is not free, so skip it when there is no AMD or Intel card and
+        // otherwise only rescan every DRM_CLIENT_INTERVAL
+        let drm_cards = self.drm.cards();
+        if drm_cards.is_empty() {
+            self.drm_clients.clear();
+            self.engine_busy.clear();
+        } else if self
+            .drm_clients_scanned
+            .is_none_or(|last| last.elapsed() >= DRM_CLIENT_INTERVAL)
+        {
+            self.drm_clients_scanned = Some(Instant::now());
+            self.drm_clients = self.drm.clients();
+            self.engine_busy = self.engine_sampler.sample(&self.drm_clients);
+        }
+        // Taken out for the tick so the card readers below can borrow self mutably
+        let clients = std::mem::take(&mut self.drm_clients);
+        let engine_busy = std::mem::take(&mut self.engine_busy);
 
-        // Detect and update AMD GPUs
-        self.update_amd_gpus();
+        // Detect and update AMD and Intel GPUs
+        self.update_drm_gpus(&drm_cards, &engine_busy);
+
+        // Attach the top consumers to each GPU; the proprietary NVIDIA driver has no DRM
+        // fdinfo counters, so its processes come from the backend
+        let nvidia_processes = if self.gpus.iter().any(|gpu| gpu.vendor == GpuVendor::Nvidia) {
+            self.nvidia.processes()
+        } else {
+            Vec::new()
+        };
+        for gpu in &mut self.gpus {
+            let mut processes = drm_processes(&clients, &engine_busy, &gpu.bus_id);
+            processes.extend(
+                nvidia_processes
+                    .iter()
+                    .filter(|(bus_id, _)| *bus_id == gpu.bus_id)
+                    .map(|(_, process)| process.clone()),
+            );
+            processes.sort_by(|a, b| {
+                b.busy_percent
+                    .unwrap_or_default()
+                    .total_cmp(&a.busy_percent.unwrap_or_default())
+                    .then_with(|| memory_bytes(b).total_cmp(&memory_bytes(a)))
+            });
+            processes.truncate(GPU_PROCESS_LIMIT);
+            gpu.processes = processes;
+        }
+        self.drm_clients = clients;
+        self.engine_busy = engine_busy;
 
         // Update min/max statistics for each GPU
         for gpu in &self.gpus {
-            let gpu_id = format!("{} {}", gpu.vendor.as_string(), &gpu.name);
+            let gpu_id = gpu.id();
 
             // Find existing stats or create new ones
             let stats_index = self.previous_gpu_states.iter().position(|s| s.id == gpu_id);
@@ -117,18 +385,22 @@ impl GpuStats {
                     stats.max_edge_temp = gpu.edge_temp;
                 }
 
-                if gpu.junction_temp < stats.min_junction_temp || stats.min_junction_temp == 0.0 {
-                    stats.min_junction_temp = gpu.junction_temp;
-                }
-                if gpu.junction_temp > stats.max_junction_temp {
-                    stats.max_junction_temp = gpu.junction_temp;
+                if let Some(junction_temp) = gpu.junction_temp {
+                    if junction_temp < stats.min_junction_temp || stats.min_junction_temp == 0.0 {
+                        stats.min_junction_temp = junction_temp;
+                    }
+                    if junction_temp > stats.max_junction_temp {
+                        stats.max_junction_temp = junction_temp;
+                    }
                 }
 
-                if gpu.memory_temp < stats.min_memory_temp || stats.min_memory_temp == 0.0 {
-                    stats.min_memory_temp = gpu.memory_temp;
-                }
-                if gpu.memory_temp > stats.max_memory_temp {
-                    stats.max_memory_temp = gpu.memory_temp;
+                if let Some(memory_temp) = gpu.memory_temp {
+                    if memory_temp < stats.min_memory_temp || stats.min_memory_temp == 0.0 {
+                        stats.min_memory_temp = memory_temp;
+                    }
+                    if memory_temp > stats.max_memory_temp {
+                        stats.max_memory_temp = memory_temp;
+                    }
                 }
             } else {
                 // Create new stats
@@ -140,103 +412,52 @@ impl GpuStats {
                     max_memory_percent: gpu.memory_percent,
                     min_edge_temp: gpu.edge_temp,
                     max_edge_temp: gpu.edge_temp,
-                    min_junction_temp: gpu.junction_temp,
-                    max_junction_temp: gpu.junction_temp,
-                    min_memory_temp: gpu.memory_temp,
-                    max_memory_temp: gpu.memory_temp,
+                    min_junction_temp: gpu.junction_temp.unwrap_or_default(),
+                    max_junction_temp: gpu.junction_temp.unwrap_or_default(),
+                    min_memory_temp: gpu.memory_temp.unwrap_or_default(),
+                    max_memory_temp: gpu.memory_temp.unwrap_or_default(),
                 });
             }
         }
 
-        // Clean up stats for removed GPUs
-        self.previous_gpu_states.retain(|stats| {
-            self.gpus.iter().any(|gpu| {
-                let gpu_id = format!("{} {}", gpu.vendor.as_string(), &gpu.name);
-                stats.id == gpu_id
-            })
-        });
+        // Stats for GPUs that disappeared are kept, so their history is still there if
+        // they come back
     }
 
-    fn update_nvidia_gpus(&mut self) {
-        // Check if nvidia-smi is available and get GPU information from it
-        if let Ok(output) = Command::new("nvidia-smi")
-            .args([
-                "--query-gpu=name,temperature.gpu,utilization.gpu,memory.used,memory.total",
-                "--format=csv,noheader,nounits",
-            ])
-            .output()
-        {
-            if output.status.success() {
-                let output_str = String::from_utf8_lossy(&output.stdout);
-                for line in output_str.lines() {
-                    let parts: Vec<&str> = line.split(',').map(|s| s.trim()).collect();
-                    if parts.len() >= 5 {
-                        let name = parts[0].to_string();
-                        let edge_temp = parts[1].parse::<f64>().unwrap_or_default();
-                        let usage_percent = parts[2].parse::<f64>().unwrap_or_default();
-                        let memory_used_mb = parts[3].parse::<u64>().unwrap_or_default();
-                        let memory_total_mb = parts[4].parse::<u64>().unwrap_or_default();
-
-                        // Convert MB to bytes for the byte-unit library
-                        let memory_used = Byte::from_u64(memory_used_mb * 1024 * 1024)
-                            .get_appropriate_unit(UnitType::Binary);
-                        let memory_total = Byte::from_u64(memory_total_mb * 1024 * 1024)
-                            .get_appropriate_unit(UnitType::Binary);
-                        let memory_percent = if memory_total_mb > 0 {
-                            (memory_used_mb as f64 / memory_total_mb as f64) * 100.0
-                        } else {
-                            0.0
-                        };
-
-                        // NVIDIA doesn't report junction and memory temps through basic SMI
-                        // commands, so we use the same value for all or leave them at 0
-                        let gpu_info = GpuInfo {
-                            name,
-                            vendor: GpuVendor::Nvidia,
-                            edge_temp,
-                            junction_temp: edge_temp, // Use the same value for junction temp
-                            memory_temp: 0.0,         // We don't have memory temp from nvidia-smi
-                            usage_percent,
-                            memory_total,
-                            memory_used,
-                            memory_percent,
-                        };
-
-                        self.gpus.push(gpu_info);
-                    }
-                }
+    fn update_drm_gpus(
+        &mut self,
+        drm_cards: &[(PathBuf, GpuVendor)],
+        engine_busy: &HashMap<(String, u64, String), f64>,
+    ) {
+        for (path, vendor) in drm_cards {
+            match vendor {
+                GpuVendor::Amd => self.add_amd_gpu_info(path),
+                // Intel has no busy counter in sysfs, so it is derived from the per-client
+                // counters
+                GpuVendor::Intel => self.add_intel_gpu_info(path, engine_busy),
+                _ => {}
             }
         }
     }
 
-    fn update_amd_gpus(&mut self) {
-        // For AMD GPUs, we need to read from sysfs entries
-        // In Linux, AMD GPU information is available in /sys/class/drm/card*/ directories
-
-        // Find all AMD GPU directories
-        let drm_path = Path::new("/sys/class/drm");
-        if !drm_path.exists() {
-            return;
+    // A card's name never changes, so lspci runs once per bus id rather than every tick
+    fn lspci_name(&mut self, bus_id: &str, vendors: &[&str]) -> Option<String> {
+        if bus_id.is_empty() {
+            return None;
         }
-
-        if let Ok(entries) = std::fs::read_dir(drm_path) {
-            for entry in entries.filter_map(Result::ok) {
-                let path = entry.path();
-                let filename = path.file_name().unwrap_or_default().to_str().unwrap_or("");
-
-                // Check if this is a card directory (e.g., card0, card1)
-                if filename.starts_with("card") && path.is_dir() {
-                    // Check if this is an AMD GPU
-                    let vendor_path = path.join("device/vendor");
-                    if let Ok(vendor) = read_file_to_string(&vendor_path) {
-                        // AMD vendor ID is 0x1002
-                        if vendor.trim() == "0x1002" {
-                            self.add_amd_gpu_info(&path);
-                        }
-                    }
+        self.lspci_names
+            .entry(bus_id.to_string())
+            .or_insert_with(|| {
+                let output = Command::new("lspci")
+                    .args(["-s", bus_id, "-vnn"])
+                    .output()
+                    .ok()?;
+                if !output.status.success() {
+                    return None;
                 }
-            }
-        }
+                extract_gpu_name_from_lspci(&String::from_utf8_lossy(&output.stdout), vendors)
+            })
+            .clone()
     }
 
     fn add_amd_gpu_info(&mut self, card_path: &Path) {
@@ -244,16 +465,10 @@ impl GpuStats {
         let mut gpu_name = String::from("AMD GPU");
 
         // Try to get the PCI bus ID from the device path
-        if let Ok(bus_id) = get_pci_bus_id_from_path(card_path) {
-            // Use lspci to get the full device name
-            if let Ok(output) = Command::new("lspci").args(["-s", &bus_id, "-vnn"]).output() {
-                if output.status.success() {
-                    let output_str = String::from_utf8_lossy(&output.stdout);
-                    // Parse the output to extract the GPU name
-                    if let Some(name) = extract_gpu_name_from_lspci(&output_str) {
-                        gpu_name = name;
-                    }
-                }
+        let bus_id = get_pci_bus_id_from_path(card_path).ok();
+        if let Some(bus_id) = &bus_id {
+            if let Some(name) = self.lspci_name(bus_id, &["AMD", "ATI"]) {
+                gpu_name = name;
             }
         }
 
@@ -269,60 +484,79 @@ impl GpuStats {
         }
 
         // Initialize GPU info
-        let default_byte = *get_default_byte();
-        let mut gpu_info = GpuInfo {
-            name: gpu_name,
-            vendor: GpuVendor::Amd,
-            edge_temp: 0.0,
-            junction_temp: 0.0,
-            memory_temp: 0.0,
-            usage_percent: 0.0,
-            memory_total: default_byte,
-            memory_used: default_byte,
-            memory_percent: 0.0,
-        };
+        let mut gpu_info = GpuInfo::new(bus_id.unwrap_or_default(), gpu_name, GpuVendor::Amd);
 
-        // Get temperatures
-        // For AMD, temperatures are usually in millidegrees Celsius
+        // Get temperatures, power, clocks, fan and voltage from the amdgpu hwmon node
         let hwmon_path = card_path.join("device/hwmon");
-        if hwmon_path.exists() {
-            if let Ok(hwmon_entries) = std::fs::read_dir(&hwmon_path) {
-                for hwmon_entry in hwmon_entries.filter_map(Result::ok) {
-                    let hwmon_dir = hwmon_entry.path();
-
-                    // Edge temperature (GPU temperature)
-                    let edge_temp_path = hwmon_dir.join("temp1_input");
-                    if edge_temp_path.exists() {
-                        if let Ok(temp_str) = read_file_to_string(&edge_temp_path) {
-                            if let Ok(temp_millicelsius) = temp_str.trim().parse::<f64>() {
-                                gpu_info.edge_temp = temp_millicelsius / 1000.0;
-                            }
-                        }
-                    }
-
-                    // Junction temperature (Hotspot)
-                    let junction_temp_path = hwmon_dir.join("temp2_input");
-                    if junction_temp_path.exists() {
-                        if let Ok(temp_str) = read_file_to_string(&junction_temp_path) {
-                            if let Ok(temp_millicelsius) = temp_str.trim().parse::<f64>() {
-                                gpu_info.junction_temp = temp_millicelsius / 1000.0;
-                            }
-                        }
-                    }
+        if let Ok(hwmon_entries) = std::fs::read_dir(&hwmon_path) {
+            for hwmon_entry in hwmon_entries.filter_map(Result::ok) {
+                let hwmon_dir = hwmon_entry.path();
 
-                    // Memory temperature
-                    let memory_temp_path = hwmon_dir.join("temp3_input");
-                    if memory_temp_path.exists() {
-                        if let Ok(temp_str) = read_file_to_string(&memory_temp_path) {
-                            if let Ok(temp_millicelsius) = temp_str.trim().parse::<f64>() {
-                                gpu_info.memory_temp = temp_millicelsius / 1000.0;
-                            }
-                        }
-                    }
+                // Temperatures are in millidegrees Celsius and labelled edge, junction and
+                // mem; APUs and older cards only have edge
+                let temp = |label: &str| {
+                    read_labelled_input(&hwmon_dir, "temp", label).map(|temp| temp / 1000.0)
+                };
+                if let Some(edge_temp) = temp("edge")
+                    .or_else(|| read_number(&hwmon_dir.join("temp1_input")).map(|t| t / 1000.0))
+                {
+                    gpu_info.edge_temp = edge_temp;
                 }
+                gpu_info.junction_temp = temp("junction");
+                gpu_info.memory_temp = temp("mem");
+
+                // Power is in microwatts; newer kernels also have power1_input
+                let watts = |file: &str| read_number(&hwmon_dir.join(file)).map(|uw| uw / 1e6);
+                gpu_info.power_draw = watts("power1_average").or_else(|| watts("power1_input"));
+                gpu_info.power_limit = watts("power1_cap");
+
+                // freq1 is sclk and freq2 is mclk, both in Hz
+                let mhz = |file: &str| read_number(&hwmon_dir.join(file)).map(|hz| hz / 1e6);
+                gpu_info.core_clock = mhz("freq1_input");
+                gpu_info.memory_clock = mhz("freq2_input");
+
+                gpu_info.fan_rpm = read_number(&hwmon_dir.join("fan1_input"));
+                gpu_info.fan_speed_percent =
+                    read_number(&hwmon_dir.join("pwm1")).map(|pwm| pwm / 255.0 * 100.0);
+
+                // Voltages are in millivolts
+                gpu_info.core_voltage =
+                    read_labelled_input(&hwmon_dir, "in", "vddgfx").map(|mv| mv / 1000.0);
             }
         }
 
+        // DPM tables list every level with the active one marked by '*'
+        let device_path = card_path.join("device");
+        if let Some((state, max_clock)) = read_dpm_table(&device_path.join("pp_dpm_sclk")) {
+            gpu_info.sclk_dpm = Some(state);
+            gpu_info.max_core_clock = Some(max_clock);
+        }
+        if let Some((state, max_clock)) = read_dpm_table(&device_path.join("pp_dpm_mclk")) {
+            gpu_info.mclk_dpm = Some(state);
+            gpu_info.max_memory_clock = Some(max_clock);
+        }
+
+        // Memory controller load
+        gpu_info.memory_util_percent = read_number(&device_path.join("mem_busy_percent"));
+
+        // GTT and CPU-visible VRAM, in bytes
+        let bytes = |file: &str| {
+            read_number(&device_path.join(file))
+                .map(|bytes| Byte::from_u64(bytes as u64).get_appropriate_unit(UnitType::Binary))
+        };
+        gpu_info.gtt_used = bytes("mem_info_gtt_used");
+        gpu_info.gtt_total = bytes("mem_info_gtt_total");
+        gpu_info.vis_vram_used = bytes("mem_info_vis_vram_used");
+        gpu_info.vis_vram_total = bytes("mem_info_vis_vram_total");
+
+        // PCIe link, e.g. "16.0 GT/s PCIe" and "16"
+        gpu_info.pcie_gen = read_file_to_string(&device_path.join("current_link_speed"))
+            .ok()
+            .and_then(|speed| pcie_generation(&speed));
+        gpu_info.pcie_width = read_number(&device_path.join("current_link_width"))
+            .map(|width| width as u32)
+            .filter(|width| *width > 0);
+
         // Get memory usage
         // For AMD, memory usage is available in /sys/class/drm/card*/device/mem_info_vram_*
         let vram_total_path = card_path.join("device/mem_info_vram_total");
@@ -360,6 +594,125 @@ impl GpuStats {
         self.gpus.push(gpu_info);
     }
 
+    fn add_intel_gpu_info(
+        &mut self,
+        card_path: &Path,
+        engine_busy: &HashMap<(String, u64, String), f64>,
+    ) {
+        let bus_id = get_pci_bus_id_from_path(card_path).unwrap_or_default();
+
+        // Get GPU name using lspci, e.g. "Intel Corporation Alder Lake-P GT2 [Iris Xe Graphics]"
+        let gpu_name = self
+            .lspci_name(&bus_id, &["Intel"])
+            .unwrap_or_else(|| String::from("Intel GPU"));
+
+        // i915 keeps the GT attributes on the card, xe under the first tile's GT
+        let driver = std::fs::read_link(card_path.join("device/driver"))
+            .ok()
+            .and_then(|link| Some(link.file_name()?.to_string_lossy().to_string()))
+            .unwrap_or_default();
+        let (freq, rc6_path) = if driver == "xe" {
+            let gt = card_path.join("device/tile0/gt0");
+            let freq = |file: &str| read_number(&gt.join("freq0").join(file));
+            (
+                [freq("act_freq"), freq("cur_freq"), freq("rp0_freq")],
+                gt.join("gtidle/idle_residency_ms"),
+            )
+        } else {
+            let freq = |file: &str| read_number(&card_path.join(file));
+            let rc6_path = if card_path.join("gt/gt0/rc6_residency_ms").exists() {
+                card_path.join("gt/gt0/rc6_residency_ms")
+            } else {
+                card_path.join("power/rc6_residency_ms")
+            };
+            (
+                [
+                    freq("gt_act_freq_mhz"),
+                    freq("gt_cur_freq_mhz"),
+                    freq("gt_RP0_freq_mhz"),
+                ],
+                rc6_path,
+            )
+        };
+
+        // Discrete Arc cards have a hwmon node with energy, power limit and temperature
+        let mut energy_uj = None;
+        let mut power_limit = None;
+        let mut edge_temp = 0.0;
+        if let Ok(hwmon_entries) = std::fs::read_dir(card_path.join("device/hwmon")) {
+            for hwmon_entry in hwmon_entries.filter_map(Result::ok) {
+                let hwmon_dir = hwmon_entry.path();
+                // xe labels the channels "card" and "pkg"; i915 only has the first one
+                energy_uj = read_labelled_input(&hwmon_dir, "energy", "card")
+                    .or_else(|| read_number(&hwmon_dir.join("energy1_input")));
+                power_limit = read_number(&hwmon_dir.join("power1_max"))
+                    .filter(|uw| *uw > 0.0)
+                    .map(|uw| uw / 1e6);
+                if let Some(temp) = read_labelled_input(&hwmon_dir, "temp", "pkg")
+                    .or_else(|| read_number(&hwmon_dir.join("temp1_input")))
+                {
+                    edge_temp = temp / 1000.0;
+                }
+            }
+        }
+
+        // RC6 residency and energy are counters, so rates need the previous sample
+        let sample = IntelSample {
+            time: Instant::now(),
+            rc6_residency_ms: read_number(&rc6_path),
+            energy_uj,
+        };
+        let (rc6_percent, power_draw) = match self.intel_samples.get(&bus_id) {
+            Some(previous) => {
+                let elapsed = sample.time.duration_since(previous.time).as_secs_f64();
+                let rate = |current: Option<f64>, previous: Option<f64>| {
+                    let delta = current? - previous?;
+                    (elapsed > 0.0 && delta >= 0.0).then_some(delta / elapsed)
+                };
+                (
+                    rate(sample.rc6_residency_ms, previous.rc6_residency_ms)
+                        .map(|ms_per_sec| (ms_per_sec / 10.0).min(100.0)),
+                    rate(sample.energy_uj, previous.energy_uj).map(|uj_per_sec| uj_per_sec / 1e6),
+                )
+            }
+            None => (None, None),
+        };
+        self.intel_samples.insert(bus_id.clone(), sample);
+
+        // Sum every client's busy share per engine class
+        let mut engines: HashMap<String, f64> = HashMap::new();
+        for ((pdev, _, class), percent) in engine_busy {
+            if pdev.trim_start_matches("0000:") == bus_id {
+                *engines.entry(class.clone()).or_default() += percent;
+            }
+        }
+        let mut engine_busy: Vec<(String, f64)> = engines
+            .into_iter()
+            .map(|(class, percent)| (class, percent.min(100.0)))
+            .collect();
+        engine_busy.sort_by(|a, b| a.0.cmp(&b.0));
+        // Report the busiest engine as the overall load, like intel_gpu_top's headline
+        let usage_percent = engine_busy
+            .iter()
+            .map(|(_, percent)| *percent)
+            .fold(0.0, f64::max);
+
+        let [core_clock, requested_core_clock, max_core_clock] = freq;
+        // Integrated GPUs share system memory, so VRAM stays at zero
+        self.gpus.push(GpuInfo {
+            edge_temp,
+            usage_percent,
+            power_draw,
+            power_limit,
+            core_clock,
+            requested_core_clock,
+            max_core_clock,
+            rc6_percent,
+            engine_busy,
+            ..GpuInfo::new(bus_id, gpu_name, GpuVendor::Intel)
+        });
+    }
+
     pub fn get_gpus(&self) -> &Vec<GpuInfo> {
         &self.gpus
     }
@@ -381,33 +734,41 @@ impl GpuStats {
             // Shorten the vendor name prefix to save space
             let display_name = gpu
                 .name
-                .replace("Advanced Micro Devices, Inc. [AMD/ATI]", "AMD");
+                .replace("Advanced Micro Devices, Inc. [AMD/ATI]", "AMD")
+                .replace("Intel Corporation", "Intel");
 
             // Get the min/max stats for this GPU
-            let gpu_id = format!("{} {}", gpu.vendor.as_string(), &gpu.name);
+            let gpu_id = gpu.id();
             let stats = self.previous_gpu_states.iter().find(|s| s.id == gpu_id);
 
             // Split model name into own line if it's long
-            mouse.add(format!("GPU {}: {}", i + 1, display_name));
+            if gpu.bus_id.is_empty() {
+                mouse.add(format!("GPU {}: {}", i + 1, display_name));
+            } else {
+                mouse.add(format!("GPU {}: {} [{}]", i + 1, display_name, gpu.bus_id));
+            }
 
             // Add temperature information with min/max
             if let Some(stats) = stats {
-                mouse.add(format!(
-                    "Temp: Edge: {:.1}°C (Min: {:.1}°C, Max: {:.1}°C)",
-                    gpu.edge_temp, stats.min_edge_temp, stats.max_edge_temp
-                ));
+                // Integrated GPUs have no temperature sensor of their own
+                if gpu.edge_temp > 0.0 {
+                    mouse.add(format!(
+                        "Temp: Edge: {:.1}°C (Min: {:.1}°C, Max: {:.1}°C)",
+                        gpu.edge_temp, stats.min_edge_temp, stats.max_edge_temp
+                    ));
+                }
 
-                if gpu.junction_temp > 0.0 {
+                if let Some(junction_temp) = gpu.junction_temp {
                     mouse.add(format!(
                         "Junct: {:.1}°C (Min: {:.1}°C, Max: {:.1}°C)",
-                        gpu.junction_temp, stats.min_junction_temp, stats.max_junction_temp
+                        junction_temp, stats.min_junction_temp, stats.max_junction_temp
                     ));
                 }
 
-                if gpu.memory_temp > 0.0 {
+                if let Some(memory_temp) = gpu.memory_temp {
                     mouse.add(format!(
                         "Mem Temp: {:.1}°C (Min: {:.1}°C, Max: {:.1}°C)",
-                        gpu.memory_temp, stats.min_memory_temp, stats.max_memory_temp
+                        memory_temp, stats.min_memory_temp, stats.max_memory_temp
                     ));
                 }
 
@@ -417,26 +778,33 @@ impl GpuStats {
                     gpu.usage_percent, stats.min_usage_percent, stats.max_usage_percent
                 ));
 
-                // Add memory information with min/max percentage
-                mouse.add(format!(
-                    "Memory: {:.2}{}/{:.2}{} ({:.1}%)",
-                    gpu.memory_used.get_value(),
-                    gpu.memory_used.get_unit(),
-                    gpu.memory_total.get_value(),
-                    gpu.memory_total.get_unit(),
-                    gpu.memory_percent
-                ));
+                // Add memory information with min/max percentage, unless the GPU
+                // shares system memory
+                if gpu.memory_total.get_value() > 0.0 {
+                    mouse.add(format!(
+                        "Memory: {:.2}{}/{:.2}{} ({:.1}%)",
+                        gpu.memory_used.get_value(),
+                        gpu.memory_used.get_unit(),
+                        gpu.memory_total.get_value(),
+                        gpu.memory_total.get_unit(),
+                        gpu.memory_percent
+                    ));
 
-                mouse.add(format!(
-                    "Mem Usage: Min: {:.1}%, Max: {:.1}%",
-                    stats.min_memory_percent, stats.max_memory_percent
-                ));
+                    mouse.add(format!(
+                        "Mem Usage: Min: {:.1}%, Max: {:.1}%",
+                        stats.min_memory_percent, stats.max_memory_percent
+                    ));
+                }
             } else {
                 // Fallback if we don't have min/max stats yet
-                mouse.add(format!(
-                    "Temp: Edge: {:.1}°C, Junction: {:.1}°C, Memory: {:.1}°C",
-                    gpu.edge_temp, gpu.junction_temp, gpu.memory_temp
-                ));
+                let mut temps = format!("Temp: Edge: {:.1}°C", gpu.edge_temp);
+                if let Some(junction_temp) = gpu.junction_temp {
+                    temps.push_str(&format!(", Junction: {:.1}°C", junction_temp));
+                }
+                if let Some(memory_temp) = gpu.memory_temp {
+                    temps.push_str(&format!(", Memory: {:.1}°C", memory_temp));
+                }
+                mouse.add(temps);
 
                 mouse.add(format!("GPU Usage: {:.1}%", gpu.usage_percent));
 
@@ -449,18 +817,516 @@ impl GpuStats {
                     gpu.memory_percent
                 ));
             }
+
+            add_gpu_details(gpu, &mut mouse);
+
+            if !gpu.processes.is_empty() {
+                mouse.add(String::from("Processes:"));
+                for process in &gpu.processes {
+                    let mut line = format!("  {} {}", process.pid, process.name);
+                    if let Some(busy) = process.busy_percent {
+                        line.push_str(&format!(" {:.1}%", busy));
+                    }
+                    if let Some(memory) = &process.memory {
+                        line.push_str(&format!(
+                            " VRAM {:.2}{}",
+                            memory.get_value(),
+                            memory.get_unit()
+                        ));
+                    }
+                    mouse.add(line);
+                }
+            }
+        }
+
+        mouse
+    }
+}
+
+// Fallback for when libnvidia-ml cannot be loaded; forks nvidia-smi on every tick
+struct NvidiaSmi {
+    fields: &'static [&'static str],
+    // Compute apps as (bus id, process), refreshed every NVIDIA_PROCESS_INTERVAL
+    processes: Vec<(String, GpuProcess)>,
+    processes_queried: Option<Instant>,
+}
+
+impl NvidiaSmi {
+    fn new() -> Self {
+        NvidiaSmi {
+            fields: &NVIDIA_QUERY_FIELDS,
+            processes: Vec::new(),
+            processes_queried: None,
+        }
+    }
+
+    fn run_process_query() -> Vec<(String, GpuProcess)> {
+        let Ok(output) = Command::new("nvidia-smi")
+            .args([
+                "--query-compute-apps=gpu_bus_id,pid,process_name,used_memory",
+                "--format=csv,noheader,nounits",
+            ])
+            .output()
+        else {
+            return Vec::new();
+        };
+        if !output.status.success() {
+            return Vec::new();
+        }
+
+        let output_str = String::from_utf8_lossy(&output.stdout);
+        output_str
+            .lines()
+            .filter_map(|line| {
+                let parts: Vec<&str> = line.split(',').map(|s| s.trim()).collect();
+                if parts.len() < 4 {
+                    return None;
+                }
+                let pid = parse_smi_value::<u32>(parts[1])?;
+                // process_name is the full executable path
+                let name = parts[2].rsplit('/').next().unwrap_or(parts[2]).to_string();
+                let memory = parse_smi_value::<u64>(parts[3]).map(|mb| {
+                    Byte::from_u64(mb * 1024 * 1024).get_appropriate_unit(UnitType::Binary)
+                });
+                Some((
+                    normalize_bus_id(parts[0]),
+                    GpuProcess {
+                        pid,
+                        name,
+                        busy_percent: None,
+                        memory,
+                    },
+                ))
+            })
+            .collect()
+    }
+
+    fn run_query(fields: &[&str]) -> Option<Vec<GpuInfo>> {
+        let output = Command::new("nvidia-smi")
+            .args([
+                &format!("--query-gpu={}", fields.join(",")),
+                "--format=csv,noheader,nounits",
+            ])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let output_str = String::from_utf8_lossy(&output.stdout);
+        Some(
+            output_str
+                .lines()
+                .filter_map(|line| parse_nvidia_line(fields, line))
+                .collect(),
+        )
+    }
+}
+
+impl NvidiaBackend for NvidiaSmi {
+    fn query(&mut self) -> Vec<GpuInfo> {
+        // Check if nvidia-smi is available and get GPU information from it
+        if let Some(gpus) = NvidiaSmi::run_query(self.fields) {
+            return gpus;
+        }
+        // Drop to the base fields for good only when they work where the extended query
+        // failed; if both fail, nvidia-smi is just not ready yet (driver still loading)
+        if self.fields.len() > NVIDIA_BASE_FIELDS.len() {
+            if let Some(gpus) = NvidiaSmi::run_query(&NVIDIA_BASE_FIELDS) {
+                self.fields = &NVIDIA_BASE_FIELDS;
+                return gpus;
+            }
+        }
+        Vec::new()
+    }
+
+    fn processes(&mut self) -> Vec<(String, GpuProcess)> {
+        if self
+            .processes_queried
+            .is_none_or(|last| last.elapsed() >= NVIDIA_PROCESS_INTERVAL)
+        {
+            self.processes_queried = Some(Instant::now());
+            self.processes = NvidiaSmi::run_process_query();
+        }
+        self.processes.clone()
+    }
+}
+
+// Source of the DRM cards and their clients, so sysfs and /proc can be swapped for a mock
+pub trait DrmBackend {
+    fn cards(&mut self) -> Vec<(PathBuf, GpuVendor)>;
+    fn clients(&mut self) -> Vec<DrmClient>;
+}
+
+struct SysfsDrm;
+
+impl DrmBackend for SysfsDrm {
+    fn cards(&mut self) -> Vec<(PathBuf, GpuVendor)> {
+        find_drm_cards()
+    }
+
+    fn clients(&mut self) -> Vec<DrmClient> {
+        scan_drm_clients()
+    }
+}
+
+// AMD and Intel cards under /sys/class/drm/card*/, which is where their sysfs data lives
+fn find_drm_cards() -> Vec<(PathBuf, GpuVendor)> {
+    let Ok(entries) = std::fs::read_dir("/sys/class/drm") else {
+        return Vec::new();
+    };
+
+    let mut cards = Vec::new();
+    for entry in entries.filter_map(Result::ok) {
+        let path = entry.path();
+        let filename = path.file_name().unwrap_or_default().to_str().unwrap_or("");
+
+        // Check if this is a card directory (e.g., card0, card1)
+        if !filename.starts_with("card") || !path.is_dir() {
+            continue;
+        }
+        // Check the PCI vendor of the card
+        let vendor = match read_file_to_string(&path.join("device/vendor")) {
+            Ok(vendor) => vendor,
+            Err(_) => continue,
+        };
+        match vendor.trim() {
+            // AMD vendor ID is 0x1002
+            "0x1002" => cards.push((path, GpuVendor::Amd)),
+            // Intel vendor ID is 0x8086
+            "0x8086" => cards.push((path, GpuVendor::Intel)),
+            _ => {}
+        }
+    }
+    cards
+}
+
+// Group the DRM clients of one card by process; a process may hold several clients
+fn drm_processes(
+    clients: &[DrmClient],
+    engine_busy: &HashMap<(String, u64, String), f64>,
+    bus_id: &str,
+) -> Vec<GpuProcess> {
+    if bus_id.is_empty() {
+        return Vec::new();
+    }
+
+    // pid -> (busy percent per engine class, VRAM in bytes)
+    let mut by_pid: HashMap<u32, (HashMap<&str, f64>, u64)> = HashMap::new();
+    for client in clients {
+        if normalize_bus_id(&client.pdev) != bus_id {
+            continue;
+        }
+        let (engines, memory) = by_pid.entry(client.pid).or_default();
+        *memory += client.vram;
+        for ((pdev, client_id, class), percent) in engine_busy {
+            if *pdev == client.pdev && *client_id == client.client_id {
+                *engines.entry(class.as_str()).or_default() += percent;
+            }
+        }
+    }
+
+    by_pid
+        .into_iter()
+        .map(|(pid, (engines, memory))| GpuProcess {
+            pid,
+            name: process_name(pid),
+            busy_percent: engines
+                .values()
+                .copied()
+                .reduce(f64::max)
+                .map(|percent| percent.min(100.0)),
+            memory: (memory > 0)
+                .then(|| Byte::from_u64(memory).get_appropriate_unit(UnitType::Binary)),
+        })
+        .collect()
+}
+
+// Short name of a running process from /proc, or "?" once it has exited
+pub fn process_name(pid: u32) -> String {
+    read_file_to_string(&Path::new("/proc").join(pid.to_string()).join("comm"))
+        .map(|comm| comm.trim().to_string())
+        .unwrap_or_else(|_| String::from("?"))
+}
+
+fn memory_bytes(process: &GpuProcess) -> f64 {
+    process
+        .memory
+        .map(|memory| memory.get_byte().as_u64() as f64)
+        .unwrap_or_default()
+}
+
+// Power, clocks, link and engine details; only the values the driver reported are shown
+fn add_gpu_details(gpu: &GpuInfo, mouse: &mut Mouse) {
+    match (gpu.power_draw, gpu.power_limit) {
+        (Some(draw), Some(limit)) => mouse.add(format!("Power: {:.2}W/{:.2}W", draw, limit)),
+        (Some(draw), None) => mouse.add(format!("Power: {:.2}W", draw)),
+        _ => {}
+    }
+
+    let clock = |name: &str, current: Option<f64>, max: Option<f64>| match (current, max) {
+        (Some(current), Some(max)) => Some(format!("{} {:.0}/{:.0}MHz", name, current, max)),
+        (Some(current), None) => Some(format!("{} {:.0}MHz", name, current)),
+        _ => None,
+    };
+    let clocks: Vec<String> = [
+        clock("Core", gpu.core_clock, gpu.max_core_clock),
+        clock("Req", gpu.requested_core_clock, None),
+        clock("Mem", gpu.memory_clock, gpu.max_memory_clock),
+        clock("SM", gpu.sm_clock, gpu.max_sm_clock),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    if !clocks.is_empty() {
+        mouse.add(format!("Clocks: {}", clocks.join(", ")));
+    }
+
+    let mut state = Vec::new();
+    match (gpu.fan_rpm, gpu.fan_speed_percent) {
+        (Some(rpm), Some(percent)) => state.push(format!("Fan: {:.0}RPM ({:.0}%)", rpm, percent)),
+        (Some(rpm), None) => state.push(format!("Fan: {:.0}RPM", rpm)),
+        (None, Some(percent)) => state.push(format!("Fan: {:.0}%", percent)),
+        _ => {}
+    }
+    if let Some(rc6) = gpu.rc6_percent {
+        state.push(format!("RC6: {:.1}%", rc6));
+    }
+    if let Some(voltage) = gpu.core_voltage {
+        state.push(format!("Vddgfx: {:.3}V", voltage));
+    }
+    if let Some(pstate) = &gpu.pstate {
+        state.push(format!("P-State: {}", pstate));
+    }
+    match (gpu.pcie_gen, gpu.pcie_width) {
+        (Some(generation), Some(width)) => {
+            state.push(format!("PCIe: Gen{} x{}", generation, width))
+        }
+        (Some(generation), None) => state.push(format!("PCIe: Gen{}", generation)),
+        _ => {}
+    }
+    if !state.is_empty() {
+        mouse.add(state.join(", "));
+    }
+
+    let engines: Vec<String> = [
+        ("Mem Ctrl", gpu.memory_util_percent),
+        ("Enc", gpu.encoder_percent),
+        ("Dec", gpu.decoder_percent),
+    ]
+    .into_iter()
+    .filter_map(|(name, percent)| Some(format!("{}: {:.0}%", name, percent?)))
+    .collect();
+    if !engines.is_empty() {
+        mouse.add(engines.join(", "));
+    }
+
+    if !gpu.engine_busy.is_empty() {
+        let engines: Vec<String> = gpu
+            .engine_busy
+            .iter()
+            .map(|(class, percent)| format!("{} {:.0}%", class, percent))
+            .collect();
+        mouse.add(format!("Engines: {}", engines.join(", ")));
+    }
+
+    let dpm: Vec<String> = [("SCLK", &gpu.sclk_dpm), ("MCLK", &gpu.mclk_dpm)]
+        .into_iter()
+        .filter_map(|(name, dpm)| {
+            let dpm = dpm.as_ref()?;
+            Some(format!("{} {}/{}", name, dpm.level, dpm.levels - 1))
+        })
+        .collect();
+    if !dpm.is_empty() {
+        mouse.add(format!("DPM: {}", dpm.join(", ")));
+    }
+
+    let memory: Vec<String> = [
+        ("GTT", &gpu.gtt_used, &gpu.gtt_total),
+        ("Vis VRAM", &gpu.vis_vram_used, &gpu.vis_vram_total),
+    ]
+    .into_iter()
+    .filter_map(|(name, used, total)| {
+        let (used, total) = (used.as_ref()?, total.as_ref()?);
+        Some(format!(
+            "{}: {:.2}{}/{:.2}{}",
+            name,
+            used.get_value(),
+            used.get_unit(),
+            total.get_value(),
+            total.get_unit()
+        ))
+    })
+    .collect();
+    if !memory.is_empty() {
+        mouse.add(memory.join(", "));
+    }
+
+    if !gpu.throttle_reasons.is_empty() {
+        mouse.add(format!("Throttle: {}", gpu.throttle_reasons.join(", ")));
+    }
+}
+
+// Parse one line of csv output for the given query fields; fields that were not queried
+// come out the same as "[N/A]"
+fn parse_nvidia_line(fields: &[&str], line: &str) -> Option<GpuInfo> {
+    let parts: Vec<&str> = line.split(',').map(|s| s.trim()).collect();
+    if parts.len() < fields.len() {
+        return None;
+    }
+
+    let raw = |field: &str| {
+        fields
+            .iter()
+            .position(|queried| *queried == field)
+            .map_or("", |index| parts[index])
+    };
+    let value = |field: &str| parse_smi_value::<f64>(raw(field));
+    let memory_used_mb = parse_smi_value::<u64>(raw("memory.used")).unwrap_or_default();
+    let memory_total_mb = parse_smi_value::<u64>(raw("memory.total")).unwrap_or_default();
+
+    // Convert MB to bytes for the byte-unit library
+    let memory_used =
+        Byte::from_u64(memory_used_mb * 1024 * 1024).get_appropriate_unit(UnitType::Binary);
+    let memory_total =
+        Byte::from_u64(memory_total_mb * 1024 * 1024).get_appropriate_unit(UnitType::Binary);
+    let memory_percent = if memory_total_mb > 0 {
+        (memory_used_mb as f64 / memory_total_mb as f64) * 100.0
+    } else {
+        0.0
+    };
+
+    Some(GpuInfo {
+        edge_temp: value("temperature.gpu").unwrap_or_default(),
+        // nvidia-smi has no hotspot reading, and only HBM and some GDDR6X boards report a
+        // memory temperature
+        memory_temp: value("temperature.memory"),
+        usage_percent: value("utilization.gpu").unwrap_or_default(),
+        memory_total,
+        memory_used,
+        memory_percent,
+        // Passively cooled cards report "[N/A]" here
+        fan_speed_percent: value("fan.speed"),
+        power_draw: value("power.draw"),
+        power_limit: value("power.limit"),
+        core_clock: value("clocks.gr"),
+        memory_clock: value("clocks.mem"),
+        sm_clock: value("clocks.sm"),
+        max_core_clock: value("clocks.max.gr"),
+        max_memory_clock: value("clocks.max.mem"),
+        max_sm_clock: value("clocks.max.sm"),
+        pstate: parse_smi_value::<String>(raw("pstate")),
+        pcie_gen: parse_smi_value::<u32>(raw("pcie.link.gen.current")),
+        pcie_width: parse_smi_value::<u32>(raw("pcie.link.width.current")),
+        memory_util_percent: value("utilization.memory"),
+        encoder_percent: value("utilization.encoder"),
+        decoder_percent: value("utilization.decoder"),
+        throttle_reasons: parse_throttle_reasons(raw("clocks_throttle_reasons.active")),
+        ..GpuInfo::new(
+            normalize_bus_id(raw("pci.bus_id")),
+            raw("name").to_string(),
+            GpuVendor::Nvidia,
+        )
+    })
+}
+
+// nvidia-smi prints "[N/A]", "[Not Supported]" and similar for values it cannot read
+fn parse_smi_value<T: std::str::FromStr>(value: &str) -> Option<T> {
+    if value.is_empty() || value.starts_with('[') {
+        return None;
+    }
+    value.parse::<T>().ok()
+}
+
+// Decode a clocks_throttle_reasons.active bitmask like "0x0000000000000004"
+fn parse_throttle_reasons(value: &str) -> Vec<String> {
+    match u64::from_str_radix(value.trim().trim_start_matches("0x"), 16) {
+        Ok(mask) => decode_throttle_reasons(mask),
+        Err(_) => Vec::new(),
+    }
+}
+
+// Names of the bits set in an NVIDIA throttle reason mask
+pub fn decode_throttle_reasons(mask: u64) -> Vec<String> {
+    NVIDIA_THROTTLE_REASONS
+        .iter()
+        .filter(|(bit, _)| mask & bit != 0)
+        .map(|(_, name)| name.to_string())
+        .collect()
+}
+
+fn read_number(path: &Path) -> Option<f64> {
+    read_file_to_string(path).ok()?.trim().parse::<f64>().ok()
+}
+
+// Read the input of the hwmon channel with the given label, e.g. ("temp", "junction")
+fn read_labelled_input(hwmon_dir: &Path, prefix: &str, label: &str) -> Option<f64> {
+    (0..16).find_map(|index| {
+        let channel_label =
+            read_file_to_string(&hwmon_dir.join(format!("{}{}_label", prefix, index))).ok()?;
+        if !channel_label.trim().eq_ignore_ascii_case(label) {
+            return None;
         }
+        read_number(&hwmon_dir.join(format!("{}{}_input", prefix, index)))
+    })
+}
+
+// Parse a pp_dpm_* table like "0: 500Mhz\n1: 1800Mhz *" into the active level and the
+// highest clock in MHz
+fn read_dpm_table(path: &Path) -> Option<(DpmState, f64)> {
+    let table = read_file_to_string(path).ok()?;
+    let mut state = DpmState {
+        level: 0,
+        levels: 0,
+    };
+    let mut max_clock: f64 = 0.0;
 
-        return mouse;
+    for line in table.lines() {
+        // RDNA cards add a deep-sleep "S:" entry that is not a real level
+        let Some((index, rest)) = line.split_once(':') else {
+            continue;
+        };
+        let Ok(index) = index.trim().parse::<u32>() else {
+            continue;
+        };
+        let clock = rest.split_whitespace().next().unwrap_or_default();
+        if let Ok(clock) = clock.to_lowercase().trim_end_matches("mhz").parse::<f64>() {
+            max_clock = max_clock.max(clock);
+        }
+        if line.trim_end().ends_with('*') {
+            state.level = index;
+        }
+        state.levels += 1;
     }
+
+    (state.levels > 0).then_some((state, max_clock))
 }
 
-// Helper function to read a file into a string
-fn read_file_to_string(path: &Path) -> io::Result<String> {
-    let mut file = File::open(path)?;
-    let mut contents = String::new();
-    file.read_to_string(&mut contents)?;
-    Ok(contents)
+// Map a link speed like "16.0 GT/s PCIe" to its PCIe generation
+fn pcie_generation(speed: &str) -> Option<u32> {
+    let transfer_rate = speed.split_whitespace().next()?.parse::<f64>().ok()?;
+    match transfer_rate {
+        rate if rate >= 64.0 => Some(6),
+        rate if rate >= 32.0 => Some(5),
+        rate if rate >= 16.0 => Some(4),
+        rate if rate >= 8.0 => Some(3),
+        rate if rate >= 5.0 => Some(2),
+        rate if rate >= 2.5 => Some(1),
+        _ => None,
+    }
+}
+
+// Bring a PCI address like nvidia-smi's "00000000:01:00.0" into the "01:00.0" form used by
+// sysfs and lspci; non-zero domains are kept
+pub fn normalize_bus_id(bus_id: &str) -> String {
+    let bus_id = bus_id.trim().to_lowercase();
+    match bus_id.split_once(':') {
+        Some((domain, rest)) if rest.contains(':') => match u32::from_str_radix(domain, 16) {
+            Ok(0) => rest.to_string(),
+            Ok(domain) => format!("{:04x}:{}", domain, rest),
+            Err(_) => bus_id,
+        },
+        _ => bus_id,
+    }
 }
 
 // Extract the PCI bus ID from a device path
@@ -474,11 +1340,7 @@ fn get_pci_bus_id_from_path(card_path: &Path) -> io::Result<String> {
     let path_str = device_path.to_string_lossy();
 
     // Find the last PCI address in the path (should be the GPU's address)
-    if let Some(last_address) = path_str
-        .split('/')
-        .filter(|s| s.starts_with("0000:"))
-        .last()
-    {
+    if let Some(last_address) = path_str.split('/').rfind(|s| s.starts_with("0000:")) {
         return Ok(last_address.trim_start_matches("0000:").to_string());
     }
 
@@ -489,13 +1351,13 @@ fn get_pci_bus_id_from_path(card_path: &Path) -> io::Result<String> {
 }
 
 // Extract the GPU name from lspci output
-fn extract_gpu_name_from_lspci(lspci_output: &str) -> Option<String> {
+fn extract_gpu_name_from_lspci(lspci_output: &str, vendors: &[&str]) -> Option<String> {
     for line in lspci_output.lines() {
         // Look for VGA or Display controller lines
         if line.contains("VGA compatible controller") || line.contains("Display controller") {
             // The format is typically:
             // "XX:XX.X VGA compatible controller [XXXX:XXXX]: AMD Radeon RX XXXX (rev XX)"
-            if line.contains("AMD") || line.contains("ATI") {
+            if vendors.iter().any(|vendor| line.contains(vendor)) {
                 // Extract just the model name part
                 if let Some(colon_pos) = line.find(": ") {
                     let name_part = &line[colon_pos + 2..];
@@ -513,3 +1375,168 @@ fn extract_gpu_name_from_lspci(lspci_output: &str) -> Option<String> {
 
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+    // Hands out one canned answer per query; an empty answer is what a failed NVML or
+    // nvidia-smi call produces
+    struct MockBackend {
+        answers: VecDeque<Vec<GpuInfo>>,
+        processes: Vec<(String, GpuProcess)>,
+    }
+
+    impl NvidiaBackend for MockBackend {
+        fn query(&mut self) -> Vec<GpuInfo> {
+            self.answers.pop_front().unwrap_or_default()
+        }
+
+        fn processes(&mut self) -> Vec<(String, GpuProcess)> {
+            self.processes.clone()
+        }
+    }
+
+    // A machine without AMD or Intel cards, so the tests never touch sysfs or /proc
+    struct NoDrm;
+
+    impl DrmBackend for NoDrm {
+        fn cards(&mut self) -> Vec<(PathBuf, GpuVendor)> {
+            Vec::new()
+        }
+
+        fn clients(&mut self) -> Vec<DrmClient> {
+            Vec::new()
+        }
+    }
+
+    // A card that reports "[N/A]" for everything optional
+    fn sparse_gpu(temp: f64, usage: f64) -> GpuInfo {
+        GpuInfo {
+            edge_temp: temp,
+            usage_percent: usage,
+            ..GpuInfo::new(
+                String::from("01:00.0"),
+                String::from("Tesla T4"),
+                GpuVendor::Nvidia,
+            )
+        }
+    }
+
+    fn nvidia_gpus(stats: &GpuStats) -> Vec<&GpuInfo> {
+        stats
+            .get_gpus()
+            .iter()
+            .filter(|gpu| gpu.vendor == GpuVendor::Nvidia)
+            .collect()
+    }
+
+    #[test]
+    fn mock_backend_with_missing_fields_and_failed_call() {
+        let backend = MockBackend {
+            answers: VecDeque::from([
+                vec![sparse_gpu(40.0, 10.0)],
+                Vec::new(),
+                vec![sparse_gpu(55.0, 80.0)],
+            ]),
+            processes: Vec::new(),
+        };
+        let mut stats = GpuStats::with_backends(Box::new(backend), Box::new(NoDrm));
+        let mut sys = SysWrapper::empty();
+
+        stats.update(&mut sys);
+        assert_eq!(nvidia_gpus(&stats).len(), 1);
+        let text = stats.get_mouse().text();
+        assert!(text.contains("GPU Usage: 10.0%"));
+        assert!(!text.contains("Power:"));
+        assert!(!text.contains("Fan:"));
+        assert!(!text.contains("P-State:"));
+
+        // A failed query drops the card from the panel without panicking
+        stats.update(&mut sys);
+        assert!(nvidia_gpus(&stats).is_empty());
+
+        // and its min/max history is still there when it answers again
+        stats.update(&mut sys);
+        let text = stats.get_mouse().text();
+        assert!(text.contains("Temp: Edge: 55.0°C (Min: 40.0°C, Max: 55.0°C)"));
+        assert!(text.contains("GPU Usage: 80.0% (Min: 10.0%, Max: 80.0%)"));
+    }
+
+    #[test]
+    fn backend_processes_are_listed_under_their_card() {
+        let process = |pid: u32, mib: u64| GpuProcess {
+            pid,
+            name: format!("worker{}", pid),
+            busy_percent: None,
+            memory: Some(Byte::from_u64(mib * 1024 * 1024).get_appropriate_unit(UnitType::Binary)),
+        };
+        let backend = MockBackend {
+            answers: VecDeque::from([vec![sparse_gpu(40.0, 10.0)]]),
+            processes: vec![
+                (String::from("01:00.0"), process(100, 512)),
+                (String::from("01:00.0"), process(200, 2048)),
+                (String::from("02:00.0"), process(300, 1024)),
+            ],
+        };
+        let mut stats = GpuStats::with_backends(Box::new(backend), Box::new(NoDrm));
+        stats.update(&mut SysWrapper::empty());
+
+        let pids: Vec<u32> = nvidia_gpus(&stats)[0]
+            .processes
+            .iter()
+            .map(|process| process.pid)
+            .collect();
+        assert_eq!(pids, vec![200, 100]);
+        assert!(stats.get_mouse().text().contains("VRAM 2.00GiB"));
+    }
+
+    #[test]
+    fn parses_extended_nvidia_line() {
+        let line = "NVIDIA GeForce RTX 4090, 54, 37, 2048, 24564, 30, 112.45, 450.00, \
+                    2520, 10501, 2520, 3120, 10501, 3120, P2, 4, 16, 62, 12, 3, 0, \
+                    0x0000000000000004, 00000000:01:00.0";
+        let gpu = parse_nvidia_line(&NVIDIA_QUERY_FIELDS, line).expect("line should parse");
+        assert_eq!(gpu.name, "NVIDIA GeForce RTX 4090");
+        assert_eq!(gpu.bus_id, "01:00.0");
+        assert_eq!(gpu.edge_temp, 54.0);
+        assert_eq!(gpu.usage_percent, 37.0);
+        assert_eq!(gpu.power_draw, Some(112.45));
+        assert_eq!(gpu.memory_temp, Some(62.0));
+        assert_eq!(gpu.pstate.as_deref(), Some("P2"));
+        assert_eq!(gpu.pcie_gen, Some(4));
+        assert_eq!(gpu.throttle_reasons, vec![String::from("SW Power Cap")]);
+    }
+
+    #[test]
+    fn leaves_unavailable_nvidia_values_absent() {
+        let line = "Tesla T4, 41, 0, 0, 15360, [N/A], 27.50, 70.00, 300, 5000, 300, \
+                    1590, 5000, 1590, P8, 3, 16, [N/A], 0, [Not Supported], \
+                    [Not Supported], [N/A], 00000000:3B:00.0";
+        let gpu = parse_nvidia_line(&NVIDIA_QUERY_FIELDS, line).expect("line should parse");
+        assert_eq!(gpu.fan_speed_percent, None);
+        assert_eq!(gpu.memory_temp, None);
+        assert_eq!(gpu.encoder_percent, None);
+        assert_eq!(gpu.decoder_percent, None);
+        assert!(gpu.throttle_reasons.is_empty());
+        assert_eq!(gpu.power_limit, Some(70.0));
+        assert_eq!(gpu.bus_id, "3b:00.0");
+    }
+
+    #[test]
+    fn parses_base_nvidia_fields() {
+        let line = "Quadro K2200, 38, 1, 310, 4043, 42, 00000000:02:00.0";
+        let gpu = parse_nvidia_line(&NVIDIA_BASE_FIELDS, line).expect("line should parse");
+        assert_eq!(gpu.name, "Quadro K2200");
+        assert_eq!(gpu.fan_speed_percent, Some(42.0));
+        assert_eq!(gpu.power_draw, None);
+        assert_eq!(gpu.pstate, None);
+        assert_eq!(gpu.bus_id, "02:00.0");
+    }
+
+    #[test]
+    fn rejects_short_nvidia_line() {
+        assert!(parse_nvidia_line(&NVIDIA_QUERY_FIELDS, "Quadro K2200, 38, 1").is_none());
+    }
+}
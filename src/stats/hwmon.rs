@@ -0,0 +1,423 @@
+use crate::{
+    common::{read_file_to_string, SysWrapper},
+    renderer::core::Mouse,
+};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+const HWMON_PATH: &str = "/sys/class/hwmon";
+// Channels that could not be read at discovery, e.g. on a runtime-suspended GPU or a
+// sleeping drivetemp disk, are tried again this often
+const RETRY_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum SensorKind {
+    Temperature,
+    Fan,
+    Voltage,
+    Power,
+    Current,
+}
+
+impl SensorKind {
+    fn from_prefix(prefix: &str) -> Option<Self> {
+        match prefix {
+            "temp" => Some(SensorKind::Temperature),
+            "fan" => Some(SensorKind::Fan),
+            "in" => Some(SensorKind::Voltage),
+            "power" => Some(SensorKind::Power),
+            "curr" => Some(SensorKind::Current),
+            _ => None,
+        }
+    }
+
+    pub fn prefix(&self) -> &str {
+        match self {
+            SensorKind::Temperature => "temp",
+            SensorKind::Fan => "fan",
+            SensorKind::Voltage => "in",
+            SensorKind::Power => "power",
+            SensorKind::Current => "curr",
+        }
+    }
+
+    // Divisor that converts the raw sysfs value into display units
+    // (millidegrees, RPM, millivolts, microwatts and milliamps respectively)
+    fn scale(&self) -> f64 {
+        match self {
+            SensorKind::Temperature => 1000.0,
+            SensorKind::Fan => 1.0,
+            SensorKind::Voltage => 1000.0,
+            SensorKind::Power => 1_000_000.0,
+            SensorKind::Current => 1000.0,
+        }
+    }
+
+    pub fn format_value(&self, value: f64) -> String {
+        match self {
+            SensorKind::Temperature => format!("{:.1}°C", value),
+            SensorKind::Fan => format!("{:.0} RPM", value),
+            SensorKind::Voltage => format!("{:.3}V", value),
+            SensorKind::Power => format!("{:.2}W", value),
+            SensorKind::Current => format!("{:.2}A", value),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Sensor {
+    pub kind: SensorKind,
+    pub index: u32,
+    pub label: String,
+    pub value: f64,
+    // Limits reported by the chip itself
+    pub min_limit: Option<f64>,
+    pub max_limit: Option<f64>,
+    pub crit_limit: Option<f64>,
+    // Session min/max of the reading
+    pub min_value: f64,
+    pub max_value: f64,
+    input_path: PathBuf,
+}
+
+impl Sensor {
+    fn discover(dir: &Path, kind: SensorKind, index: u32, input_file: &str) -> Option<Self> {
+        let attr = |suffix: &str| dir.join(format!("{}{}_{}", kind.prefix(), index, suffix));
+
+        let label = read_file_to_string(&attr("label"))
+            .map(|label| label.trim().to_string())
+            .ok()
+            .filter(|label| !label.is_empty())
+            .unwrap_or_else(|| format!("{}{}", kind.prefix(), index));
+
+        // Super-I/O chips whose limits the BIOS never programmed read 0/0 (or some other
+        // empty window), which would flag every channel
+        let (mut min_limit, mut max_limit) = (
+            read_scaled(&attr("min"), kind),
+            read_scaled(&attr("max"), kind),
+        );
+        if let (Some(min), Some(max)) = (min_limit, max_limit) {
+            if max <= min {
+                (min_limit, max_limit) = (None, None);
+            }
+        }
+
+        let mut sensor = Sensor {
+            kind,
+            index,
+            label,
+            value: 0.0,
+            min_limit,
+            max_limit,
+            crit_limit: read_scaled(&attr("crit"), kind),
+            min_value: 0.0,
+            max_value: 0.0,
+            input_path: dir.join(input_file),
+        };
+
+        // Skip channels whose input cannot be read (e.g. disabled fan headers)
+        sensor.value = read_scaled(&sensor.input_path, kind)?;
+        sensor.min_value = sensor.value;
+        sensor.max_value = sensor.value;
+        Some(sensor)
+    }
+
+    // Whether the reading falls outside the limits programmed into the chip
+    pub fn out_of_range(&self) -> bool {
+        self.min_limit.is_some_and(|min| self.value < min)
+            || self.max_limit.is_some_and(|max| self.value > max)
+    }
+
+    fn refresh(&mut self) {
+        if let Some(value) = read_scaled(&self.input_path, self.kind) {
+            self.value = value;
+            self.min_value = self.min_value.min(value);
+            self.max_value = self.max_value.max(value);
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct HwmonChip {
+    pub name: String,
+    pub hwmon: String,
+    pub path: PathBuf,
+    // Resolved `device` link; unlike hwmonN it stays with the hardware across hotplug
+    device: Option<PathBuf>,
+    pub sensors: Vec<Sensor>,
+    // Input files of the channels that failed to read, keyed like the sensors
+    unreadable: Vec<((SensorKind, u32), String)>,
+}
+
+impl HwmonChip {
+    fn discover(hwmon_dir: &Path) -> Option<Self> {
+        // Very old kernels keep the attributes under device/ instead of the hwmon node
+        let dir = if hwmon_dir.join("name").exists() {
+            hwmon_dir.to_path_buf()
+        } else {
+            hwmon_dir.join("device")
+        };
+
+        let name = read_file_to_string(&dir.join("name"))
+            .ok()?
+            .trim()
+            .to_string();
+        let hwmon = hwmon_dir
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+
+        // Collect the input file for every channel, keyed so they sort by kind then index
+        let mut channels: BTreeMap<(SensorKind, u32), String> = BTreeMap::new();
+        if let Ok(entries) = std::fs::read_dir(&dir) {
+            for entry in entries.filter_map(Result::ok) {
+                let file_name = entry.file_name().to_string_lossy().to_string();
+                if let Some((kind, index, attribute)) = parse_channel_file(&file_name) {
+                    match attribute {
+                        "input" => {
+                            channels.insert((kind, index), file_name);
+                        }
+                        // Some power meters only expose an averaged reading
+                        "average" if kind == SensorKind::Power => {
+                            channels.entry((kind, index)).or_insert(file_name);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        let mut sensors = Vec::new();
+        let mut unreadable = Vec::new();
+        for ((kind, index), file) in channels {
+            match Sensor::discover(&dir, kind, index, &file) {
+                Some(sensor) => sensors.push(sensor),
+                None => unreadable.push(((kind, index), file)),
+            }
+        }
+
+        Some(HwmonChip {
+            name,
+            hwmon,
+            path: dir,
+            device: std::fs::canonicalize(hwmon_dir.join("device")).ok(),
+            sensors,
+            unreadable,
+        })
+    }
+
+    pub fn display_name(&self) -> String {
+        format!("{} ({})", self.name, self.hwmon)
+    }
+
+    // The device the chip belongs to (PCI function, NVMe controller, SCSI disk, I2C client, ...)
+    pub fn device_path(&self) -> Option<PathBuf> {
+        self.device.clone()
+    }
+
+    // Stable identity for history: the kernel hands out freed hwmonN numbers to new chips
+    pub fn id(&self) -> String {
+        match &self.device {
+            Some(device) => format!("{}@{}", self.name, device.display()),
+            None => format!("{}@{}", self.name, self.path.display()),
+        }
+    }
+
+    pub fn sensors_of(&self, kind: SensorKind) -> impl Iterator<Item = &Sensor> {
+        self.sensors
+            .iter()
+            .filter(move |sensor| sensor.kind == kind)
+    }
+
+    // Case-insensitive lookup of a channel by its label
+    pub fn find_sensor(&self, kind: SensorKind, label: &str) -> Option<&Sensor> {
+        self.sensors_of(kind)
+            .find(|sensor| sensor.label.eq_ignore_ascii_case(label))
+    }
+
+    pub fn sensor(&self, kind: SensorKind, index: u32) -> Option<&Sensor> {
+        self.sensors_of(kind).find(|sensor| sensor.index == index)
+    }
+
+    fn refresh(&mut self) {
+        for sensor in &mut self.sensors {
+            sensor.refresh();
+        }
+    }
+
+    // Add the channels that have become readable since discovery
+    fn retry_unreadable(&mut self) {
+        let mut found = false;
+        let path = &self.path;
+        self.unreadable.retain(|((kind, index), file)| {
+            match Sensor::discover(path, *kind, *index, file) {
+                Some(sensor) => {
+                    self.sensors.push(sensor);
+                    found = true;
+                    false
+                }
+                None => true,
+            }
+        });
+        if found {
+            self.sensors
+                .sort_by_key(|sensor| (sensor.kind, sensor.index));
+        }
+    }
+}
+
+// Every hwmon chip on the system, refreshed once per tick
+#[derive(Clone)]
+pub struct Hwmon {
+    chips: Vec<HwmonChip>,
+    // hwmonN entries seen at the last discovery, used to detect hotplug
+    entries: Vec<String>,
+    last_retry: Option<Instant>,
+}
+
+impl Hwmon {
+    pub fn new() -> Self {
+        Hwmon {
+            chips: Vec::new(),
+            entries: Vec::new(),
+            last_retry: None,
+        }
+    }
+
+    pub fn refresh(&mut self) {
+        let hwmon_path = Path::new(HWMON_PATH);
+        let mut entries: Vec<String> = match std::fs::read_dir(hwmon_path) {
+            Ok(entries) => entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+        entries.sort_by_key(|entry| hwmon_number(entry));
+
+        if entries != self.entries {
+            // Chips came or went; rediscover but keep session min/max for known channels
+            let previous = std::mem::take(&mut self.chips);
+            self.chips = entries
+                .iter()
+                .filter_map(|entry| HwmonChip::discover(&hwmon_path.join(entry)))
+                .collect();
+
+            for chip in &mut self.chips {
+                if let Some(old) = previous.iter().find(|old| old.id() == chip.id()) {
+                    for sensor in &mut chip.sensors {
+                        if let Some(old_sensor) = old.sensor(sensor.kind, sensor.index) {
+                            sensor.min_value = sensor.min_value.min(old_sensor.min_value);
+                            sensor.max_value = sensor.max_value.max(old_sensor.max_value);
+                        }
+                    }
+                }
+            }
+            self.entries = entries;
+            self.last_retry = Some(Instant::now());
+        } else {
+            let retry = self
+                .last_retry
+                .is_none_or(|last| last.elapsed() >= RETRY_INTERVAL);
+            if retry {
+                self.last_retry = Some(Instant::now());
+            }
+            for chip in &mut self.chips {
+                chip.refresh();
+                if retry {
+                    chip.retry_unreadable();
+                }
+            }
+        }
+    }
+
+    pub fn chips(&self) -> &Vec<HwmonChip> {
+        &self.chips
+    }
+
+    pub fn chips_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a HwmonChip> {
+        self.chips.iter().filter(move |chip| chip.name == name)
+    }
+}
+
+pub struct SensorStats {
+    chips: Vec<HwmonChip>,
+}
+
+impl SensorStats {
+    pub fn new() -> Self {
+        SensorStats { chips: Vec::new() }
+    }
+
+    pub fn update(&mut self, sys_obj: &mut SysWrapper) {
+        sys_obj.hwmon.refresh();
+        self.chips = sys_obj
+            .hwmon
+            .chips()
+            .iter()
+            .filter(|chip| !chip.sensors.is_empty())
+            .cloned()
+            .collect();
+    }
+
+    pub fn get_mouse(&self) -> Mouse {
+        let title = String::from("Sensors");
+        let mut mouse = Mouse::new(title);
+
+        if self.chips.is_empty() {
+            mouse.add(String::from("No hwmon sensors detected"));
+            return mouse;
+        }
+
+        for (i, chip) in self.chips.iter().enumerate() {
+            if i > 0 {
+                mouse.add(String::from("")); // Add empty line between chips
+            }
+            mouse.add(chip.display_name());
+
+            for sensor in &chip.sensors {
+                let mut line = format!(
+                    "  {}: {} (Min: {}, Max: {})",
+                    sensor.label,
+                    sensor.kind.format_value(sensor.value),
+                    sensor.kind.format_value(sensor.min_value),
+                    sensor.kind.format_value(sensor.max_value)
+                );
+                if let Some(crit) = sensor.crit_limit {
+                    line.push_str(&format!(" Crit: {}", sensor.kind.format_value(crit)));
+                }
+                if sensor.out_of_range() {
+                    line.push_str(" [!]");
+                }
+                mouse.add(line);
+            }
+        }
+
+        mouse
+    }
+}
+
+// Split a hwmon attribute name like "temp3_input" into (kind, index, attribute)
+fn parse_channel_file(file_name: &str) -> Option<(SensorKind, u32, &str)> {
+    let (channel, attribute) = file_name.split_once('_')?;
+    let digits_at = channel.find(|c: char| c.is_ascii_digit())?;
+    let (prefix, index) = channel.split_at(digits_at);
+    let kind = SensorKind::from_prefix(prefix)?;
+    let index = index.parse::<u32>().ok()?;
+    Some((kind, index, attribute))
+}
+
+fn read_scaled(path: &Path, kind: SensorKind) -> Option<f64> {
+    let raw = read_file_to_string(path).ok()?;
+    let raw = raw.trim().parse::<f64>().ok()?;
+    Some(raw / kind.scale())
+}
+
+fn hwmon_number(entry: &str) -> u32 {
+    entry
+        .trim_start_matches("hwmon")
+        .parse::<u32>()
+        .unwrap_or(u32::MAX)
+}
@@ -1,8 +1,11 @@
 use crate::{
-    common::{get_default_byte, SysWrapper},
+    common::{get_default_byte, read_file_to_string, SysWrapper},
     renderer::core::Mouse,
+    stats::hwmon::{HwmonChip, Sensor, SensorKind},
 };
 use byte_unit::{AdjustedByte, Byte, UnitType};
+use std::collections::HashMap;
+use std::path::Path;
 
 pub struct MemStats {
     total_mem: AdjustedByte,
@@ -11,6 +14,43 @@ pub struct MemStats {
     percentage_used: f64,
     min_mem_usage: AdjustedByte,
     max_mem_usage: AdjustedByte,
+    // Swap
+    total_swap: AdjustedByte,
+    swap_usage: AdjustedByte,
+    swap_percentage_used: f64,
+    // None until the first sample; idle swap is often exactly zero
+    min_swap_usage: Option<AdjustedByte>,
+    max_swap_usage: Option<AdjustedByte>,
+    // Kernel memory breakdown from /proc/meminfo
+    cached: AdjustedByte,
+    buffers: AdjustedByte,
+    shmem: AdjustedByte,
+    slab: AdjustedByte,
+    dirty: AdjustedByte,
+    writeback: AdjustedByte,
+    hugepages_total: u64,
+    hugepages_free: u64,
+    hugepage_size: AdjustedByte,
+    zram_devices: Vec<ZramInfo>,
+    dimms: Vec<DimmTemp>,
+}
+
+#[derive(Clone)]
+struct ZramInfo {
+    name: String,
+    // Uncompressed size of the data stored on the device
+    orig_data: AdjustedByte,
+    // Memory actually used to hold it, including allocator overhead
+    mem_used: AdjustedByte,
+    compression_ratio: f64,
+}
+
+#[derive(Clone)]
+struct DimmTemp {
+    // e.g. "DIMM 2 (i2c-0 0x52)"
+    label: String,
+    // Min/max come from the hwmon session tracking
+    sensor: Sensor,
 }
 
 impl MemStats {
@@ -23,6 +63,22 @@ impl MemStats {
             percentage_used: 1.00,
             min_mem_usage: default_byte,
             max_mem_usage: default_byte,
+            total_swap: default_byte,
+            swap_usage: default_byte,
+            swap_percentage_used: 0.0,
+            min_swap_usage: None,
+            max_swap_usage: None,
+            cached: default_byte,
+            buffers: default_byte,
+            shmem: default_byte,
+            slab: default_byte,
+            dirty: default_byte,
+            writeback: default_byte,
+            hugepages_total: 0,
+            hugepages_free: 0,
+            hugepage_size: default_byte,
+            zram_devices: Vec::new(),
+            dimms: Vec::new(),
         }
     }
 
@@ -45,6 +101,115 @@ impl MemStats {
         } else {
             self.min_mem_usage = self.min_mem_usage.min(self.mem_usage);
         }
+
+        self.update_meminfo();
+        self.update_zram();
+        self.update_dimms(sys_obj);
+    }
+
+    fn update_meminfo(&mut self) {
+        let meminfo = match read_file_to_string(Path::new("/proc/meminfo")) {
+            Ok(contents) => parse_meminfo(&contents),
+            Err(_) => return,
+        };
+        let kib = |key: &str| {
+            Byte::from_u64(meminfo.get(key).copied().unwrap_or_default() * 1024)
+                .get_appropriate_unit(UnitType::Binary)
+        };
+
+        // Swap usage with min/max, same as RAM
+        let swap_total_kib = meminfo.get("SwapTotal").copied().unwrap_or_default();
+        let swap_free_kib = meminfo.get("SwapFree").copied().unwrap_or_default();
+        let swap_used_kib = swap_total_kib.saturating_sub(swap_free_kib);
+        self.total_swap = kib("SwapTotal");
+        self.swap_usage =
+            Byte::from_u64(swap_used_kib * 1024).get_appropriate_unit(UnitType::Binary);
+        self.swap_percentage_used = if swap_total_kib > 0 {
+            ((swap_used_kib as f64 / swap_total_kib as f64) * 100.00).round()
+        } else {
+            0.0
+        };
+
+        let swap_usage = self.swap_usage;
+        self.max_swap_usage = Some(
+            self.max_swap_usage
+                .map_or(swap_usage, |max| max.max(swap_usage)),
+        );
+        self.min_swap_usage = Some(
+            self.min_swap_usage
+                .map_or(swap_usage, |min| min.min(swap_usage)),
+        );
+
+        self.cached = kib("Cached");
+        self.buffers = kib("Buffers");
+        self.shmem = kib("Shmem");
+        self.slab = kib("Slab");
+        self.dirty = kib("Dirty");
+        self.writeback = kib("Writeback");
+
+        // HugePages_* are page counts rather than sizes
+        self.hugepages_total = meminfo.get("HugePages_Total").copied().unwrap_or_default();
+        self.hugepages_free = meminfo.get("HugePages_Free").copied().unwrap_or_default();
+        self.hugepage_size = kib("Hugepagesize");
+    }
+
+    fn update_zram(&mut self) {
+        self.zram_devices.clear();
+
+        let block_path = Path::new("/sys/block");
+        if let Ok(entries) = std::fs::read_dir(block_path) {
+            for entry in entries.filter_map(Result::ok) {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if !name.starts_with("zram") {
+                    continue;
+                }
+
+                // mm_stat: orig_data_size compr_data_size mem_used_total mem_limit ...
+                let Ok(mm_stat) = read_file_to_string(&entry.path().join("mm_stat")) else {
+                    continue;
+                };
+                let fields: Vec<u64> = mm_stat
+                    .split_whitespace()
+                    .map(|field| field.parse::<u64>().unwrap_or_default())
+                    .collect();
+                if fields.len() < 3 {
+                    continue;
+                }
+
+                let (orig_data, compr_data, mem_used) = (fields[0], fields[1], fields[2]);
+                self.zram_devices.push(ZramInfo {
+                    name,
+                    orig_data: Byte::from_u64(orig_data).get_appropriate_unit(UnitType::Binary),
+                    mem_used: Byte::from_u64(mem_used).get_appropriate_unit(UnitType::Binary),
+                    compression_ratio: if compr_data > 0 {
+                        orig_data as f64 / compr_data as f64
+                    } else {
+                        0.0
+                    },
+                });
+            }
+        }
+
+        self.zram_devices.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+
+    fn update_dimms(&mut self, sys_obj: &SysWrapper) {
+        self.dimms.clear();
+
+        // jc42 covers the thermal sensor on DDR4 modules, spd5118 the SPD hub on DDR5
+        for chip in sys_obj.hwmon.chips() {
+            if chip.name != "jc42" && chip.name != "spd5118" {
+                continue;
+            }
+            if let Some(sensor) = chip.sensor(SensorKind::Temperature, 1) {
+                self.dimms.push(DimmTemp {
+                    label: dimm_label(chip),
+                    sensor: sensor.clone(),
+                });
+            }
+        }
+
+        self.dimms.sort_by(|a, b| a.label.cmp(&b.label));
     }
 
     pub fn get_mouse(&mut self) -> Mouse {
@@ -79,6 +244,122 @@ impl MemStats {
             self.available_mem.get_unit()
         ));
 
-        return mouse;
+        // Swap usage and its min/max
+        if self.total_swap.get_value() > 0.0 {
+            mouse.add(format!(
+                "Swap: {:.2}{}/{:.2}{} ({:.1}%)",
+                self.swap_usage.get_value(),
+                self.swap_usage.get_unit(),
+                self.total_swap.get_value(),
+                self.total_swap.get_unit(),
+                self.swap_percentage_used
+            ));
+            if let (Some(max), Some(min)) = (self.max_swap_usage, self.min_swap_usage) {
+                mouse.add(format!(
+                    "Swap Max: {:.2}{}, Min: {:.2}{}",
+                    max.get_value(),
+                    max.get_unit(),
+                    min.get_value(),
+                    min.get_unit()
+                ));
+            }
+        } else {
+            mouse.add(String::from("Swap: disabled"));
+        }
+
+        // Kernel memory breakdown, two values per line to save vertical space
+        mouse.add(format!(
+            "Cached: {:.2}{}, Buffers: {:.2}{}",
+            self.cached.get_value(),
+            self.cached.get_unit(),
+            self.buffers.get_value(),
+            self.buffers.get_unit()
+        ));
+        mouse.add(format!(
+            "Shmem: {:.2}{}, Slab: {:.2}{}",
+            self.shmem.get_value(),
+            self.shmem.get_unit(),
+            self.slab.get_value(),
+            self.slab.get_unit()
+        ));
+        mouse.add(format!(
+            "Dirty: {:.2}{}, Writeback: {:.2}{}",
+            self.dirty.get_value(),
+            self.dirty.get_unit(),
+            self.writeback.get_value(),
+            self.writeback.get_unit()
+        ));
+
+        if self.hugepages_total > 0 {
+            mouse.add(format!(
+                "HugePages: {}/{} used ({:.0}{} each)",
+                self.hugepages_total - self.hugepages_free.min(self.hugepages_total),
+                self.hugepages_total,
+                self.hugepage_size.get_value(),
+                self.hugepage_size.get_unit()
+            ));
+        }
+
+        for zram in &self.zram_devices {
+            mouse.add(format!(
+                "{}: {:.2}{} in {:.2}{} ({:.2}x)",
+                zram.name,
+                zram.orig_data.get_value(),
+                zram.orig_data.get_unit(),
+                zram.mem_used.get_value(),
+                zram.mem_used.get_unit(),
+                zram.compression_ratio
+            ));
+        }
+
+        for dimm in &self.dimms {
+            let sensor = &dimm.sensor;
+            let mut line = format!(
+                "{}: {:.1}°C (Min: {:.1}°C, Max: {:.1}°C)",
+                dimm.label, sensor.value, sensor.min_value, sensor.max_value
+            );
+            if let Some(crit) = sensor.crit_limit {
+                line.push_str(&format!(" Crit: {:.1}°C", crit));
+                if sensor.value >= crit {
+                    line.push_str(" [HOT]");
+                }
+            }
+            mouse.add(line);
+        }
+
+        mouse
     }
 }
+
+// Name a DIMM sensor after its I2C device, e.g. ".../i2c-0/0-0052" -> "DIMM 2 (i2c-0 0x52)"
+fn dimm_label(chip: &HwmonChip) -> String {
+    let device = chip
+        .device_path()
+        .and_then(|path| Some(path.file_name()?.to_string_lossy().to_string()));
+    let Some((bus, address)) = device.as_deref().and_then(|name| name.split_once('-')) else {
+        return format!("DIMM ({})", chip.hwmon);
+    };
+    let Ok(address) = u16::from_str_radix(address, 16) else {
+        return format!("DIMM ({})", chip.hwmon);
+    };
+
+    // Slots are numbered by address: SPD EEPROMs sit at 0x50-0x57, jc42 sensors at 0x18-0x1f
+    let base = if chip.name == "spd5118" { 0x50 } else { 0x18 };
+    match address.checked_sub(base).filter(|slot| *slot < 8) {
+        Some(slot) => format!("DIMM {} (i2c-{} 0x{:02x})", slot, bus, address),
+        None => format!("DIMM (i2c-{} 0x{:02x})", bus, address),
+    }
+}
+
+// Parse /proc/meminfo lines like "Cached:  1234 kB" into a key -> value map (values in kB,
+// or plain counts for the HugePages_* entries)
+fn parse_meminfo(contents: &str) -> HashMap<String, u64> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            let value = value.split_whitespace().next()?.parse::<u64>().ok()?;
+            Some((key.trim().to_string(), value))
+        })
+        .collect()
+}
@@ -1,3 +1,19 @@
 pub mod cpu;
+pub mod disk;
+pub mod drm_fdinfo;
+pub mod fan;
 pub mod gpu;
+pub mod hwmon;
 pub mod mem;
+pub mod net;
+pub mod nvml;
+pub mod power;
+pub mod power_supply;
+pub mod process;
+pub mod process_tree;
+pub mod psi;
+pub mod smart;
+pub mod storage;
+pub mod thermal;
+pub mod topology;
+pub mod voltage;
@@ -0,0 +1,186 @@
+use crate::{
+    common::{format_rate, read_file_to_string, SysWrapper},
+    renderer::core::Mouse,
+};
+use std::path::Path;
+use std::time::Instant;
+
+#[derive(Clone)]
+pub struct NetStats {
+    interfaces: Vec<InterfaceInfo>,
+    last_update: Option<Instant>,
+    // Loopback, bridges, veth pairs, tunnels and the like are hidden unless asked for
+    show_virtual: bool,
+}
+
+#[derive(Clone, Copy)]
+struct InterfaceCounters {
+    rx_bytes: u64,
+    rx_packets: u64,
+    rx_errors: u64,
+    rx_drops: u64,
+    tx_bytes: u64,
+    tx_packets: u64,
+    tx_errors: u64,
+    tx_drops: u64,
+}
+
+#[derive(Clone)]
+pub struct InterfaceInfo {
+    pub name: String,
+    pub is_virtual: bool,
+    counters: InterfaceCounters,
+    // Rates since the previous tick
+    pub rx_bytes_per_sec: f64,
+    pub tx_bytes_per_sec: f64,
+    pub rx_packets_per_sec: f64,
+    pub tx_packets_per_sec: f64,
+    pub peak_rx_bytes_per_sec: f64,
+    pub peak_tx_bytes_per_sec: f64,
+}
+
+impl NetStats {
+    pub fn new() -> Self {
+        NetStats {
+            interfaces: Vec::new(),
+            last_update: None,
+            show_virtual: false,
+        }
+    }
+
+    pub fn toggle_virtual(&mut self) {
+        self.show_virtual = !self.show_virtual;
+    }
+
+    pub fn update(&mut self, _sys_obj: &mut SysWrapper) {
+        let contents = match read_file_to_string(Path::new("/proc/net/dev")) {
+            Ok(contents) => contents,
+            Err(_) => return,
+        };
+
+        let now = Instant::now();
+        let elapsed = self
+            .last_update
+            .map(|last| now.duration_since(last).as_secs_f64())
+            .unwrap_or(0.0);
+
+        let mut interfaces = Vec::new();
+        for (name, counters) in parse_proc_net_dev(&contents) {
+            let is_virtual = Path::new("/sys/devices/virtual/net").join(&name).exists();
+            let mut info = InterfaceInfo {
+                name,
+                is_virtual,
+                counters,
+                rx_bytes_per_sec: 0.0,
+                tx_bytes_per_sec: 0.0,
+                rx_packets_per_sec: 0.0,
+                tx_packets_per_sec: 0.0,
+                peak_rx_bytes_per_sec: 0.0,
+                peak_tx_bytes_per_sec: 0.0,
+            };
+
+            if let Some(previous) = self.interfaces.iter().find(|i| i.name == info.name) {
+                if elapsed > 0.0 {
+                    // Counters reset when an interface is re-created, so never go negative
+                    let rate = |now: u64, before: u64| now.saturating_sub(before) as f64 / elapsed;
+                    info.rx_bytes_per_sec = rate(counters.rx_bytes, previous.counters.rx_bytes);
+                    info.tx_bytes_per_sec = rate(counters.tx_bytes, previous.counters.tx_bytes);
+                    info.rx_packets_per_sec =
+                        rate(counters.rx_packets, previous.counters.rx_packets);
+                    info.tx_packets_per_sec =
+                        rate(counters.tx_packets, previous.counters.tx_packets);
+                }
+                info.peak_rx_bytes_per_sec =
+                    previous.peak_rx_bytes_per_sec.max(info.rx_bytes_per_sec);
+                info.peak_tx_bytes_per_sec =
+                    previous.peak_tx_bytes_per_sec.max(info.tx_bytes_per_sec);
+            }
+
+            interfaces.push(info);
+        }
+
+        self.interfaces = interfaces;
+        self.last_update = Some(now);
+    }
+
+    pub fn get_mouse(&self) -> Mouse {
+        let title = String::from("Network");
+        let mut mouse = Mouse::new(title);
+
+        let visible: Vec<&InterfaceInfo> = self
+            .interfaces
+            .iter()
+            .filter(|interface| self.show_virtual || !interface.is_virtual)
+            .collect();
+
+        if visible.is_empty() {
+            mouse.add(String::from("No network interfaces detected"));
+            return mouse;
+        }
+
+        for (i, interface) in visible.iter().enumerate() {
+            if i > 0 {
+                mouse.add(String::from("")); // Add empty line between interfaces
+            }
+
+            mouse.add(interface.name.clone());
+            mouse.add(format!(
+                "  RX: {} ({:.0} pkt/s) Peak: {}",
+                format_rate(interface.rx_bytes_per_sec),
+                interface.rx_packets_per_sec,
+                format_rate(interface.peak_rx_bytes_per_sec)
+            ));
+            mouse.add(format!(
+                "  TX: {} ({:.0} pkt/s) Peak: {}",
+                format_rate(interface.tx_bytes_per_sec),
+                interface.tx_packets_per_sec,
+                format_rate(interface.peak_tx_bytes_per_sec)
+            ));
+            mouse.add(format!(
+                "  Errors: RX {}, TX {}  Drops: RX {}, TX {}",
+                interface.counters.rx_errors,
+                interface.counters.tx_errors,
+                interface.counters.rx_drops,
+                interface.counters.tx_drops
+            ));
+        }
+
+        mouse
+    }
+}
+
+// Parse the per-interface counters out of /proc/net/dev. The first two lines are headers,
+// each following line looks like:
+// "  eth0: rx_bytes rx_packets rx_errs rx_drop fifo frame compressed multicast tx_bytes ..."
+fn parse_proc_net_dev(contents: &str) -> Vec<(String, InterfaceCounters)> {
+    let mut interfaces = Vec::new();
+
+    for line in contents.lines().skip(2) {
+        let Some((name, fields)) = line.split_once(':') else {
+            continue;
+        };
+        let fields: Vec<u64> = fields
+            .split_whitespace()
+            .map(|field| field.parse::<u64>().unwrap_or_default())
+            .collect();
+        if fields.len() < 16 {
+            continue;
+        }
+
+        interfaces.push((
+            name.trim().to_string(),
+            InterfaceCounters {
+                rx_bytes: fields[0],
+                rx_packets: fields[1],
+                rx_errors: fields[2],
+                rx_drops: fields[3],
+                tx_bytes: fields[8],
+                tx_packets: fields[9],
+                tx_errors: fields[10],
+                tx_drops: fields[11],
+            },
+        ));
+    }
+
+    interfaces
+}
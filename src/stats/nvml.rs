@@ -0,0 +1,422 @@
+use crate::stats::gpu::{
+    decode_throttle_reasons, normalize_bus_id, process_name, GpuInfo, GpuProcess, GpuVendor,
+};
+use byte_unit::{Byte, UnitType};
+use std::ffi::{c_char, c_int, c_uint, c_ulonglong, c_void, CStr, CString};
+
+// Sonames tried in order; the unversioned one only exists with the development package
+const NVML_LIBRARIES: [&str; 2] = ["libnvidia-ml.so.1", "libnvidia-ml.so"];
+// Overrides the library path, e.g. to point at a fake NVML for testing
+const NVML_LIBRARY_ENV: &str = "RSENSOR_NVML_LIBRARY";
+
+// nvmlReturn_t, nvmlDevice_t and the enum values used below, from nvml.h
+type NvmlReturn = c_int;
+type NvmlDevice = *mut c_void;
+// Getters for a single value, a value selected by a sensor or clock type, and a
+// utilization with its sampling period
+type UintGetter = unsafe extern "C" fn(NvmlDevice, *mut c_uint) -> NvmlReturn;
+type TypedGetter = unsafe extern "C" fn(NvmlDevice, c_int, *mut c_uint) -> NvmlReturn;
+type CodecGetter = unsafe extern "C" fn(NvmlDevice, *mut c_uint, *mut c_uint) -> NvmlReturn;
+type ProcessGetter =
+    unsafe extern "C" fn(NvmlDevice, *mut c_uint, *mut NvmlProcessInfo) -> NvmlReturn;
+const NVML_SUCCESS: NvmlReturn = 0;
+const NVML_ERROR_INSUFFICIENT_SIZE: NvmlReturn = 7;
+// usedGpuMemory when the driver cannot tell, e.g. on Windows WDDM or without permission
+const NVML_VALUE_NOT_AVAILABLE: c_ulonglong = c_ulonglong::MAX;
+const NVML_TEMPERATURE_GPU: c_int = 0;
+const NVML_CLOCK_GRAPHICS: c_int = 0;
+const NVML_CLOCK_SM: c_int = 1;
+const NVML_CLOCK_MEM: c_int = 2;
+const NVML_PSTATE_UNKNOWN: c_int = 32;
+const NVML_DEVICE_NAME_BUFFER_SIZE: usize = 96;
+
+#[repr(C)]
+#[derive(Default)]
+struct NvmlUtilization {
+    gpu: c_uint,
+    memory: c_uint,
+}
+
+#[repr(C)]
+#[derive(Default)]
+struct NvmlMemory {
+    total: c_ulonglong,
+    #[allow(dead_code)]
+    free: c_ulonglong,
+    used: c_ulonglong,
+}
+
+#[repr(C)]
+struct NvmlPciInfo {
+    bus_id_legacy: [c_char; 16],
+    domain: c_uint,
+    bus: c_uint,
+    device: c_uint,
+    pci_device_id: c_uint,
+    pci_subsystem_id: c_uint,
+    // "00000000:01:00.0"
+    bus_id: [c_char; 32],
+}
+
+// nvmlProcessInfo_t as taken by the _v2 and _v3 process queries
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+struct NvmlProcessInfo {
+    pid: c_uint,
+    used_gpu_memory: c_ulonglong,
+    #[allow(dead_code)]
+    gpu_instance_id: c_uint,
+    #[allow(dead_code)]
+    compute_instance_id: c_uint,
+}
+
+// Source of NVIDIA GPU readings, so the NVML and nvidia-smi paths (or a mock) can be
+// swapped behind GpuStats
+pub trait NvidiaBackend {
+    fn query(&mut self) -> Vec<GpuInfo>;
+    // Processes on each card as (bus id, process); only VRAM use is known
+    fn processes(&mut self) -> Vec<(String, GpuProcess)>;
+}
+
+// Entry points resolved from libnvidia-ml; the optional ones are missing on old drivers
+struct NvmlFunctions {
+    shutdown: unsafe extern "C" fn() -> NvmlReturn,
+    device_get_count: unsafe extern "C" fn(*mut c_uint) -> NvmlReturn,
+    device_get_handle_by_index: unsafe extern "C" fn(c_uint, *mut NvmlDevice) -> NvmlReturn,
+    device_get_name: unsafe extern "C" fn(NvmlDevice, *mut c_char, c_uint) -> NvmlReturn,
+    device_get_pci_info: Option<unsafe extern "C" fn(NvmlDevice, *mut NvmlPciInfo) -> NvmlReturn>,
+    device_get_temperature: Option<TypedGetter>,
+    device_get_utilization_rates:
+        Option<unsafe extern "C" fn(NvmlDevice, *mut NvmlUtilization) -> NvmlReturn>,
+    device_get_memory_info: Option<unsafe extern "C" fn(NvmlDevice, *mut NvmlMemory) -> NvmlReturn>,
+    device_get_power_usage: Option<UintGetter>,
+    device_get_enforced_power_limit: Option<UintGetter>,
+    device_get_clock_info: Option<TypedGetter>,
+    device_get_max_clock_info: Option<TypedGetter>,
+    device_get_fan_speed: Option<UintGetter>,
+    device_get_performance_state:
+        Option<unsafe extern "C" fn(NvmlDevice, *mut c_int) -> NvmlReturn>,
+    device_get_curr_pcie_link_generation: Option<UintGetter>,
+    device_get_curr_pcie_link_width: Option<UintGetter>,
+    device_get_encoder_utilization: Option<CodecGetter>,
+    device_get_decoder_utilization: Option<CodecGetter>,
+    device_get_current_clocks_throttle_reasons:
+        Option<unsafe extern "C" fn(NvmlDevice, *mut c_ulonglong) -> NvmlReturn>,
+    device_get_compute_running_processes: Option<ProcessGetter>,
+    device_get_graphics_running_processes: Option<ProcessGetter>,
+}
+
+// libnvidia-ml loaded with dlopen, so there is no link-time dependency on the driver
+pub struct Nvml {
+    handle: *mut c_void,
+    functions: NvmlFunctions,
+}
+
+impl Nvml {
+    // Load and initialise NVML, or None if the library or the driver is not there
+    pub fn load() -> Option<Self> {
+        match std::env::var(NVML_LIBRARY_ENV) {
+            Ok(path) => Self::open(&path),
+            Err(_) => NVML_LIBRARIES
+                .iter()
+                .find_map(|library| Self::open(library)),
+        }
+    }
+
+    pub fn open(library: &str) -> Option<Self> {
+        let library = CString::new(library).ok()?;
+        // SAFETY: dlopen with a valid C string; a null handle is checked below
+        let flags = libc::RTLD_NOW | libc::RTLD_LOCAL;
+        let handle = unsafe { libc::dlopen(library.as_ptr(), flags) };
+        if handle.is_null() {
+            return None;
+        }
+
+        // SAFETY: the symbol types match the prototypes in nvml.h
+        let loaded = unsafe {
+            let init: Option<unsafe extern "C" fn() -> NvmlReturn> = symbol(handle, c"nvmlInit_v2");
+            let functions = load_functions(handle);
+            match (init, functions) {
+                (Some(init), Some(functions)) if init() == NVML_SUCCESS => Some(functions),
+                _ => None,
+            }
+        };
+
+        match loaded {
+            Some(functions) => Some(Nvml { handle, functions }),
+            None => {
+                // SAFETY: handle came from dlopen and nothing borrowed from it survives
+                unsafe { libc::dlclose(handle) };
+                None
+            }
+        }
+    }
+
+    fn device_info(&self, device: NvmlDevice) -> Option<GpuInfo> {
+        let f = &self.functions;
+
+        let mut name_buffer = [0 as c_char; NVML_DEVICE_NAME_BUFFER_SIZE];
+        // SAFETY: the buffer length matches what is passed in
+        let result = unsafe {
+            (f.device_get_name)(
+                device,
+                name_buffer.as_mut_ptr(),
+                name_buffer.len() as c_uint,
+            )
+        };
+        if result != NVML_SUCCESS {
+            return None;
+        }
+        // SAFETY: NVML always NUL-terminates the name on success
+        let name = unsafe { CStr::from_ptr(name_buffer.as_ptr()) }
+            .to_string_lossy()
+            .to_string();
+        let mut gpu = GpuInfo::new(self.bus_id(device), name, GpuVendor::Nvidia);
+
+        // Each query is optional: NVML_ERROR_NOT_SUPPORTED and friends leave the field absent
+        let uint = |getter: Option<UintGetter>| {
+            let mut value: c_uint = 0;
+            // SAFETY: valid device handle and out pointer
+            (unsafe { getter?(device, &mut value) } == NVML_SUCCESS).then_some(value as f64)
+        };
+        let by_type = |getter: Option<TypedGetter>, clock_type: c_int| {
+            let mut value: c_uint = 0;
+            // SAFETY: valid device handle and out pointer
+            (unsafe { getter?(device, clock_type, &mut value) } == NVML_SUCCESS)
+                .then_some(value as f64)
+        };
+        let codec = |getter: Option<CodecGetter>| {
+            let (mut value, mut sampling_period): (c_uint, c_uint) = (0, 0);
+            // SAFETY: valid device handle and out pointers
+            (unsafe { getter?(device, &mut value, &mut sampling_period) } == NVML_SUCCESS)
+                .then_some(value as f64)
+        };
+
+        if let Some(temp) = by_type(f.device_get_temperature, NVML_TEMPERATURE_GPU) {
+            gpu.edge_temp = temp;
+        }
+
+        let mut utilization = NvmlUtilization::default();
+        if let Some(getter) = f.device_get_utilization_rates {
+            // SAFETY: valid device handle and out pointer
+            if unsafe { getter(device, &mut utilization) } == NVML_SUCCESS {
+                gpu.usage_percent = utilization.gpu as f64;
+                gpu.memory_util_percent = Some(utilization.memory as f64);
+            }
+        }
+
+        let mut memory = NvmlMemory::default();
+        if let Some(getter) = f.device_get_memory_info {
+            // SAFETY: valid device handle and out pointer
+            if unsafe { getter(device, &mut memory) } == NVML_SUCCESS {
+                gpu.memory_used =
+                    Byte::from_u64(memory.used).get_appropriate_unit(UnitType::Binary);
+                gpu.memory_total =
+                    Byte::from_u64(memory.total).get_appropriate_unit(UnitType::Binary);
+                if memory.total > 0 {
+                    gpu.memory_percent = memory.used as f64 / memory.total as f64 * 100.0;
+                }
+            }
+        }
+
+        // Power is reported in milliwatts
+        gpu.power_draw = uint(f.device_get_power_usage).map(|mw| mw / 1000.0);
+        gpu.power_limit = uint(f.device_get_enforced_power_limit).map(|mw| mw / 1000.0);
+
+        gpu.core_clock = by_type(f.device_get_clock_info, NVML_CLOCK_GRAPHICS);
+        gpu.memory_clock = by_type(f.device_get_clock_info, NVML_CLOCK_MEM);
+        gpu.sm_clock = by_type(f.device_get_clock_info, NVML_CLOCK_SM);
+        gpu.max_core_clock = by_type(f.device_get_max_clock_info, NVML_CLOCK_GRAPHICS);
+        gpu.max_memory_clock = by_type(f.device_get_max_clock_info, NVML_CLOCK_MEM);
+        gpu.max_sm_clock = by_type(f.device_get_max_clock_info, NVML_CLOCK_SM);
+
+        gpu.fan_speed_percent = uint(f.device_get_fan_speed);
+
+        if let Some(getter) = f.device_get_performance_state {
+            let mut pstate: c_int = NVML_PSTATE_UNKNOWN;
+            // SAFETY: valid device handle and out pointer
+            if unsafe { getter(device, &mut pstate) } == NVML_SUCCESS
+                && pstate != NVML_PSTATE_UNKNOWN
+            {
+                gpu.pstate = Some(format!("P{}", pstate));
+            }
+        }
+
+        gpu.pcie_gen = uint(f.device_get_curr_pcie_link_generation).map(|gen| gen as u32);
+        gpu.pcie_width = uint(f.device_get_curr_pcie_link_width).map(|width| width as u32);
+
+        gpu.encoder_percent = codec(f.device_get_encoder_utilization);
+        gpu.decoder_percent = codec(f.device_get_decoder_utilization);
+
+        if let Some(getter) = f.device_get_current_clocks_throttle_reasons {
+            let mut reasons: c_ulonglong = 0;
+            // SAFETY: valid device handle and out pointer
+            if unsafe { getter(device, &mut reasons) } == NVML_SUCCESS {
+                gpu.throttle_reasons = decode_throttle_reasons(reasons);
+            }
+        }
+
+        Some(gpu)
+    }
+
+    // PCI address as used for the DRM cards, or empty if NVML cannot tell
+    fn bus_id(&self, device: NvmlDevice) -> String {
+        let Some(getter) = self.functions.device_get_pci_info else {
+            return String::new();
+        };
+        // SAFETY: all-zero is a valid NvmlPciInfo
+        let mut pci: NvmlPciInfo = unsafe { std::mem::zeroed() };
+        // SAFETY: valid device handle and out pointer; busId is NUL-terminated
+        if unsafe { getter(device, &mut pci) } != NVML_SUCCESS {
+            return String::new();
+        }
+        normalize_bus_id(&unsafe { CStr::from_ptr(pci.bus_id.as_ptr()) }.to_string_lossy())
+    }
+
+    // Every device handle NVML knows about
+    fn devices(&self) -> Vec<NvmlDevice> {
+        let f = &self.functions;
+        let mut count: c_uint = 0;
+        // SAFETY: valid out pointer
+        if unsafe { (f.device_get_count)(&mut count) } != NVML_SUCCESS {
+            return Vec::new();
+        }
+
+        (0..count)
+            .filter_map(|index| {
+                let mut device: NvmlDevice = std::ptr::null_mut();
+                // SAFETY: valid out pointer; the handle stays valid until nvmlShutdown
+                let result = unsafe { (f.device_get_handle_by_index)(index, &mut device) };
+                (result == NVML_SUCCESS).then_some(device)
+            })
+            .collect()
+    }
+}
+
+// Ask one of the running process queries, growing the buffer when NVML says it is too
+// small; processes can start between the two calls, so this is retried a few times
+fn running_processes(getter: ProcessGetter, device: NvmlDevice) -> Vec<NvmlProcessInfo> {
+    let mut infos = vec![NvmlProcessInfo::default(); 32];
+    for _ in 0..3 {
+        let mut count = infos.len() as c_uint;
+        // SAFETY: count is the length of the buffer NVML writes into
+        match unsafe { getter(device, &mut count, infos.as_mut_ptr()) } {
+            NVML_SUCCESS => {
+                infos.truncate(count as usize);
+                return infos;
+            }
+            NVML_ERROR_INSUFFICIENT_SIZE => {
+                infos.resize(count as usize + 8, NvmlProcessInfo::default());
+            }
+            _ => break,
+        }
+    }
+    Vec::new()
+}
+
+impl NvidiaBackend for Nvml {
+    fn query(&mut self) -> Vec<GpuInfo> {
+        self.devices()
+            .into_iter()
+            .filter_map(|device| self.device_info(device))
+            .collect()
+    }
+
+    fn processes(&mut self) -> Vec<(String, GpuProcess)> {
+        let f = &self.functions;
+        let mut processes = Vec::new();
+        for device in self.devices() {
+            let bus_id = self.bus_id(device);
+            // A process doing both compute and graphics is listed by both queries
+            let mut seen: Vec<u32> = Vec::new();
+            let getters = [
+                f.device_get_compute_running_processes,
+                f.device_get_graphics_running_processes,
+            ];
+            for info in getters
+                .into_iter()
+                .flatten()
+                .flat_map(|getter| running_processes(getter, device))
+            {
+                if seen.contains(&info.pid) {
+                    continue;
+                }
+                seen.push(info.pid);
+                processes.push((
+                    bus_id.clone(),
+                    GpuProcess {
+                        pid: info.pid,
+                        name: process_name(info.pid),
+                        busy_percent: None,
+                        memory: (info.used_gpu_memory != NVML_VALUE_NOT_AVAILABLE).then(|| {
+                            Byte::from_u64(info.used_gpu_memory)
+                                .get_appropriate_unit(UnitType::Binary)
+                        }),
+                    },
+                ));
+            }
+        }
+        processes
+    }
+}
+
+impl Drop for Nvml {
+    fn drop(&mut self) {
+        // SAFETY: NVML was initialised in open and the handle came from dlopen
+        unsafe {
+            (self.functions.shutdown)();
+            libc::dlclose(self.handle);
+        }
+    }
+}
+
+// SAFETY: the caller must ask for the type matching the C prototype of the symbol
+unsafe fn symbol<T: Copy>(handle: *mut c_void, name: &CStr) -> Option<T> {
+    let pointer = libc::dlsym(handle, name.as_ptr());
+    if pointer.is_null() {
+        return None;
+    }
+    Some(std::mem::transmute_copy::<*mut c_void, T>(&pointer))
+}
+
+// SAFETY: handle must be a live dlopen handle for libnvidia-ml
+unsafe fn load_functions(handle: *mut c_void) -> Option<NvmlFunctions> {
+    Some(NvmlFunctions {
+        shutdown: symbol(handle, c"nvmlShutdown")?,
+        device_get_count: symbol(handle, c"nvmlDeviceGetCount_v2")?,
+        device_get_handle_by_index: symbol(handle, c"nvmlDeviceGetHandleByIndex_v2")?,
+        device_get_name: symbol(handle, c"nvmlDeviceGetName")?,
+        device_get_pci_info: symbol(handle, c"nvmlDeviceGetPciInfo_v3"),
+        device_get_temperature: symbol(handle, c"nvmlDeviceGetTemperature"),
+        device_get_utilization_rates: symbol(handle, c"nvmlDeviceGetUtilizationRates"),
+        device_get_memory_info: symbol(handle, c"nvmlDeviceGetMemoryInfo"),
+        device_get_power_usage: symbol(handle, c"nvmlDeviceGetPowerUsage"),
+        device_get_enforced_power_limit: symbol(handle, c"nvmlDeviceGetEnforcedPowerLimit"),
+        device_get_clock_info: symbol(handle, c"nvmlDeviceGetClockInfo"),
+        device_get_max_clock_info: symbol(handle, c"nvmlDeviceGetMaxClockInfo"),
+        device_get_fan_speed: symbol(handle, c"nvmlDeviceGetFanSpeed"),
+        device_get_performance_state: symbol(handle, c"nvmlDeviceGetPerformanceState"),
+        device_get_curr_pcie_link_generation: symbol(
+            handle,
+            c"nvmlDeviceGetCurrPcieLinkGeneration",
+        ),
+        device_get_curr_pcie_link_width: symbol(handle, c"nvmlDeviceGetCurrPcieLinkWidth"),
+        device_get_encoder_utilization: symbol(handle, c"nvmlDeviceGetEncoderUtilization"),
+        device_get_decoder_utilization: symbol(handle, c"nvmlDeviceGetDecoderUtilization"),
+        device_get_current_clocks_throttle_reasons: symbol(
+            handle,
+            c"nvmlDeviceGetCurrentClocksThrottleReasons",
+        ),
+        // _v2 has the same layout and is all that drivers before 510 export
+        device_get_compute_running_processes: symbol(
+            handle,
+            c"nvmlDeviceGetComputeRunningProcesses_v3",
+        )
+        .or_else(|| symbol(handle, c"nvmlDeviceGetComputeRunningProcesses_v2")),
+        device_get_graphics_running_processes: symbol(
+            handle,
+            c"nvmlDeviceGetGraphicsRunningProcesses_v3",
+        )
+        .or_else(|| symbol(handle, c"nvmlDeviceGetGraphicsRunningProcesses_v2")),
+    })
+}
@@ -0,0 +1,237 @@
+use crate::{
+    common::read_file_to_string,
+    renderer::core::Mouse,
+    stats::hwmon::{Hwmon, SensorKind},
+};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+const POWERCAP_PATH: &str = "/sys/class/powercap";
+
+// Power draw per RAPL domain and hwmon power channel, shown in the CPU panel
+#[derive(Clone)]
+pub struct PowerStats {
+    domains: Vec<PowerDomain>,
+    rapl_discovered: bool,
+    // energy_uj is root-only on kernels patched for the PLATYPUS side channel
+    rapl_denied: bool,
+    last_update: Option<Instant>,
+}
+
+#[derive(Clone)]
+enum PowerSource {
+    // Cumulative energy counter in microjoules that wraps at max_energy_range_uj
+    Rapl {
+        energy_path: PathBuf,
+        max_energy_uj: u64,
+        last_energy_uj: Option<u64>,
+    },
+    // Instantaneous reading already converted to watts by the hwmon module
+    Hwmon,
+}
+
+#[derive(Clone)]
+struct PowerDomain {
+    id: String,
+    name: String,
+    source: PowerSource,
+    watts: Option<f64>,
+    min_watts: f64,
+    max_watts: f64,
+    // Energy used and time covered since the session started
+    joules: f64,
+    seconds: f64,
+}
+
+impl PowerDomain {
+    fn record(&mut self, watts: f64, elapsed: f64) {
+        if self.watts.is_none() {
+            self.min_watts = watts;
+            self.max_watts = watts;
+        }
+        self.watts = Some(watts);
+        self.min_watts = self.min_watts.min(watts);
+        self.max_watts = self.max_watts.max(watts);
+        self.joules += watts * elapsed;
+        self.seconds += elapsed;
+    }
+
+    fn average_watts(&self) -> f64 {
+        if self.seconds > 0.0 {
+            self.joules / self.seconds
+        } else {
+            0.0
+        }
+    }
+}
+
+impl PowerStats {
+    pub fn new() -> Self {
+        PowerStats {
+            domains: Vec::new(),
+            rapl_discovered: false,
+            rapl_denied: false,
+            last_update: None,
+        }
+    }
+
+    pub fn update(&mut self, hwmon: &Hwmon) {
+        if !self.rapl_discovered {
+            self.discover_rapl();
+            self.rapl_discovered = true;
+        }
+
+        let now = Instant::now();
+        let elapsed = self
+            .last_update
+            .map(|last| now.duration_since(last).as_secs_f64())
+            .unwrap_or(0.0);
+        self.last_update = Some(now);
+
+        for domain in &mut self.domains {
+            let mut watts = None;
+            if let PowerSource::Rapl {
+                energy_path,
+                max_energy_uj,
+                last_energy_uj,
+            } = &mut domain.source
+            {
+                let Some(energy_uj) = read_u64(energy_path) else {
+                    continue;
+                };
+
+                if let Some(last) = *last_energy_uj {
+                    if elapsed > 0.0 {
+                        // The counter wraps around at max_energy_range_uj
+                        let delta_uj = if energy_uj >= last {
+                            energy_uj - last
+                        } else {
+                            max_energy_uj.saturating_sub(last) + energy_uj
+                        };
+                        let joules = delta_uj as f64 / 1_000_000.0;
+                        watts = Some(joules / elapsed);
+                    }
+                }
+                *last_energy_uj = Some(energy_uj);
+            }
+
+            if let Some(watts) = watts {
+                domain.record(watts, elapsed);
+            }
+        }
+
+        // hwmon power channels (amdgpu PPT, zenpower, ...) come and go with their chips
+        let mut seen = Vec::new();
+        for chip in hwmon.chips() {
+            for sensor in chip.sensors_of(SensorKind::Power) {
+                let id = format!("{}/power{}", chip.id(), sensor.index);
+                let position = match self.domains.iter().position(|d| d.id == id) {
+                    Some(position) => position,
+                    None => {
+                        self.domains.push(PowerDomain {
+                            id: id.clone(),
+                            name: format!("{} {}", chip.name, sensor.label),
+                            source: PowerSource::Hwmon,
+                            watts: None,
+                            min_watts: 0.0,
+                            max_watts: 0.0,
+                            joules: 0.0,
+                            seconds: 0.0,
+                        });
+                        self.domains.len() - 1
+                    }
+                };
+                self.domains[position].record(sensor.value, elapsed);
+                seen.push(id);
+            }
+        }
+        self.domains.retain(|domain| {
+            matches!(domain.source, PowerSource::Rapl { .. }) || seen.contains(&domain.id)
+        });
+    }
+
+    fn discover_rapl(&mut self) {
+        let Ok(entries) = std::fs::read_dir(POWERCAP_PATH) else {
+            return;
+        };
+
+        let mut zones: Vec<String> = entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            // intel-rapl-mmio duplicates the package domain on newer Intel parts
+            .filter(|zone| zone.starts_with("intel-rapl:"))
+            .collect();
+        zones.sort();
+
+        for zone in zones {
+            let zone_path = Path::new(POWERCAP_PATH).join(&zone);
+            let zone_name = |path: &Path| {
+                read_file_to_string(&path.join("name"))
+                    .map(|name| name.trim().to_string())
+                    .unwrap_or_default()
+            };
+
+            // Sub-zones (intel-rapl:0:1) are named relative to their package
+            let mut name = zone_name(&zone_path);
+            let parts: Vec<&str> = zone.split(':').collect();
+            if parts.len() > 2 {
+                let parent = Path::new(POWERCAP_PATH).join(format!("{}:{}", parts[0], parts[1]));
+                name = format!("{} {}", zone_name(&parent), name);
+            }
+
+            let energy_path = zone_path.join("energy_uj");
+            if read_u64(&energy_path).is_none() {
+                self.rapl_denied |= energy_path.exists();
+                continue;
+            }
+
+            self.domains.push(PowerDomain {
+                id: zone,
+                name,
+                source: PowerSource::Rapl {
+                    energy_path,
+                    max_energy_uj: read_u64(&zone_path.join("max_energy_range_uj"))
+                        .unwrap_or(u64::MAX),
+                    last_energy_uj: None,
+                },
+                watts: None,
+                min_watts: 0.0,
+                max_watts: 0.0,
+                joules: 0.0,
+                seconds: 0.0,
+            });
+        }
+    }
+
+    pub fn add_to_mouse(&self, mouse: &mut Mouse) {
+        let domains: Vec<&PowerDomain> = self
+            .domains
+            .iter()
+            .filter(|domain| domain.watts.is_some())
+            .collect();
+
+        if domains.is_empty() {
+            if self.rapl_denied {
+                mouse.add(String::from("Power: RAPL energy counters need root"));
+            }
+            return;
+        }
+
+        mouse.add(String::from("Power:"));
+        for domain in domains {
+            mouse.add(format!(
+                "  {}: {:.2}W (Min: {:.2}W, Max: {:.2}W, Avg: {:.2}W) Session: {:.1}J",
+                domain.name,
+                domain.watts.unwrap_or_default(),
+                domain.min_watts,
+                domain.max_watts,
+                domain.average_watts(),
+                domain.joules
+            ));
+        }
+    }
+}
+
+fn read_u64(path: &Path) -> Option<u64> {
+    read_file_to_string(path).ok()?.trim().parse::<u64>().ok()
+}
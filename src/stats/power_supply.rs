@@ -0,0 +1,202 @@
+use crate::{
+    common::{read_file_to_string, SysWrapper},
+    renderer::core::Mouse,
+};
+use std::path::Path;
+
+const POWER_SUPPLY_PATH: &str = "/sys/class/power_supply";
+
+#[derive(Clone)]
+pub struct PowerSupplyStats {
+    batteries: Vec<BatteryInfo>,
+    // None when the machine has no mains adapter (or does not report one)
+    ac_online: Option<bool>,
+}
+
+#[derive(Clone)]
+pub struct BatteryInfo {
+    pub name: String,
+    pub status: String,
+    pub capacity: Option<f64>,
+    // Energy in Wh and power in W; charge_* batteries are converted using the voltage
+    pub energy_now: Option<f64>,
+    pub energy_full: Option<f64>,
+    pub energy_full_design: Option<f64>,
+    pub power: Option<f64>,
+    pub voltage: Option<f64>,
+    pub cycle_count: Option<u64>,
+}
+
+impl BatteryInfo {
+    fn read(path: &Path, name: String) -> Self {
+        let read_value = |file: &str| -> Option<f64> {
+            read_file_to_string(&path.join(file))
+                .ok()?
+                .trim()
+                .parse::<f64>()
+                .ok()
+        };
+
+        // Everything in sysfs is micro-units (µWh, µAh, µW, µA, µV)
+        let voltage = read_value("voltage_now").map(|uv| uv / 1_000_000.0);
+        let energy = |energy_file: &str, charge_file: &str| {
+            read_value(energy_file)
+                .map(|uwh| uwh / 1_000_000.0)
+                .or_else(|| Some(read_value(charge_file)? / 1_000_000.0 * voltage?))
+        };
+        let power = read_value("power_now")
+            .map(|uw| uw / 1_000_000.0)
+            .or_else(|| Some(read_value("current_now")? / 1_000_000.0 * voltage?));
+
+        BatteryInfo {
+            name,
+            status: read_file_to_string(&path.join("status"))
+                .map(|status| status.trim().to_string())
+                .unwrap_or_else(|_| String::from("Unknown")),
+            capacity: read_value("capacity"),
+            energy_now: energy("energy_now", "charge_now"),
+            energy_full: energy("energy_full", "charge_full"),
+            energy_full_design: energy("energy_full_design", "charge_full_design"),
+            // Some firmware reports discharge as a negative current
+            power: power.map(f64::abs),
+            voltage,
+            cycle_count: read_value("cycle_count")
+                .map(|cycles| cycles as u64)
+                .filter(|cycles| *cycles > 0),
+        }
+    }
+
+    // Hours until empty when discharging, or until full when charging
+    pub fn time_remaining(&self) -> Option<f64> {
+        let power = self.power.filter(|power| *power > 0.0)?;
+        match self.status.as_str() {
+            "Discharging" => Some(self.energy_now? / power),
+            "Charging" => Some((self.energy_full? - self.energy_now?).max(0.0) / power),
+            _ => None,
+        }
+    }
+
+    // Percentage of design capacity that has been lost
+    pub fn wear_level(&self) -> Option<f64> {
+        let full = self.energy_full?;
+        let design = self.energy_full_design.filter(|design| *design > 0.0)?;
+        Some(((1.0 - full / design) * 100.0).max(0.0))
+    }
+}
+
+impl PowerSupplyStats {
+    pub fn new() -> Self {
+        PowerSupplyStats {
+            batteries: Vec::new(),
+            ac_online: None,
+        }
+    }
+
+    pub fn update(&mut self, _sys_obj: &mut SysWrapper) {
+        self.batteries.clear();
+        self.ac_online = None;
+
+        let Ok(entries) = std::fs::read_dir(POWER_SUPPLY_PATH) else {
+            return;
+        };
+
+        let mut entries: Vec<_> = entries.filter_map(Result::ok).collect();
+        entries.sort_by_key(|entry| entry.file_name());
+
+        for entry in entries {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            let read_attr = |file: &str| {
+                read_file_to_string(&path.join(file))
+                    .map(|value| value.trim().to_string())
+                    .unwrap_or_default()
+            };
+
+            match read_attr("type").as_str() {
+                // Peripheral batteries (mice, headsets) report scope "Device"
+                "Battery" if read_attr("scope") != "Device" => {
+                    self.batteries.push(BatteryInfo::read(&path, name));
+                }
+                "Mains" => {
+                    let online = read_attr("online") == "1";
+                    self.ac_online = Some(self.ac_online.unwrap_or(false) || online);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    pub fn has_battery(&self) -> bool {
+        !self.batteries.is_empty()
+    }
+
+    pub fn get_mouse(&self) -> Mouse {
+        let title = String::from("Battery");
+        let mut mouse = Mouse::new(title);
+
+        if let Some(online) = self.ac_online {
+            mouse.add(format!(
+                "AC Adapter: {}",
+                if online { "Online" } else { "Offline" }
+            ));
+        }
+
+        for battery in &self.batteries {
+            mouse.add(format!(
+                "{}: {:.0}% ({})",
+                battery.name,
+                battery.capacity.unwrap_or_default(),
+                battery.status
+            ));
+
+            if let Some(power) = battery.power {
+                let direction = match battery.status.as_str() {
+                    "Charging" => "Charge Rate",
+                    "Discharging" => "Discharge Rate",
+                    _ => "Rate",
+                };
+                mouse.add(format!("  {}: {:.2}W", direction, power));
+            }
+
+            if let Some(hours) = battery.time_remaining() {
+                let minutes = (hours * 60.0).round() as u64;
+                let until = if battery.status == "Charging" {
+                    "until full"
+                } else {
+                    "remaining"
+                };
+                mouse.add(format!(
+                    "  Time: {}h {:02}m {}",
+                    minutes / 60,
+                    minutes % 60,
+                    until
+                ));
+            }
+
+            if let (Some(now), Some(full)) = (battery.energy_now, battery.energy_full) {
+                mouse.add(format!("  Energy: {:.1}Wh/{:.1}Wh", now, full));
+            }
+
+            if let Some(wear) = battery.wear_level() {
+                mouse.add(format!(
+                    "  Wear: {:.1}% (Design: {:.1}Wh)",
+                    wear,
+                    battery.energy_full_design.unwrap_or_default()
+                ));
+            }
+
+            let mut details = Vec::new();
+            if let Some(voltage) = battery.voltage {
+                details.push(format!("Voltage: {:.2}V", voltage));
+            }
+            if let Some(cycles) = battery.cycle_count {
+                details.push(format!("Cycles: {}", cycles));
+            }
+            if !details.is_empty() {
+                mouse.add(format!("  {}", details.join(", ")));
+            }
+        }
+
+        mouse
+    }
+}
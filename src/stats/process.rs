@@ -0,0 +1,368 @@
+use crate::{
+    common::{format_rate, SysWrapper},
+    renderer::core::Mouse,
+};
+use byte_unit::{Byte, UnitType};
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::time::Instant;
+use sysinfo::{Process, ProcessRefreshKind, ProcessesToUpdate, ThreadKind, Uid, UpdateKind, Users};
+
+// Rows of the table shown at once until set_height sizes it to the terminal; the rest
+// is reached by scrolling
+const PROCESS_ROWS: usize = 20;
+// Borders and the header line
+const PANEL_LINES: usize = 3;
+const COMMAND_WIDTH: usize = 60;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum SortColumn {
+    Pid,
+    User,
+    Cpu,
+    Memory,
+    DiskRead,
+    DiskWrite,
+    Command,
+}
+
+impl SortColumn {
+    const ALL: [SortColumn; 7] = [
+        SortColumn::Pid,
+        SortColumn::User,
+        SortColumn::Cpu,
+        SortColumn::Memory,
+        SortColumn::DiskRead,
+        SortColumn::DiskWrite,
+        SortColumn::Command,
+    ];
+
+    fn header(&self) -> &str {
+        match self {
+            SortColumn::Pid => "PID",
+            SortColumn::User => "USER",
+            SortColumn::Cpu => "CPU%",
+            SortColumn::Memory => "MEM",
+            SortColumn::DiskRead => "READ/s",
+            SortColumn::DiskWrite => "WRITE/s",
+            SortColumn::Command => "COMMAND",
+        }
+    }
+
+    // Column width and whether it is right-aligned
+    fn layout(&self) -> (usize, bool) {
+        match self {
+            SortColumn::Pid => (7, true),
+            SortColumn::User => (10, false),
+            SortColumn::Cpu => (6, true),
+            SortColumn::Memory => (10, true),
+            SortColumn::DiskRead | SortColumn::DiskWrite => (12, true),
+            SortColumn::Command => (COMMAND_WIDTH, false),
+        }
+    }
+
+    fn index(&self) -> usize {
+        SortColumn::ALL
+            .iter()
+            .position(|column| column == self)
+            .unwrap_or_default()
+    }
+
+    // Names and commands read best A-Z, numbers biggest first
+    fn descending_by_default(&self) -> bool {
+        !matches!(self, SortColumn::User | SortColumn::Command)
+    }
+}
+
+#[derive(Clone)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    pub user: String,
+    // Percent of one core, so busy multithreaded processes go above 100
+    pub cpu_percent: f64,
+    pub memory: u64,
+    // Bytes per second since the previous refresh
+    pub read_rate: f64,
+    pub write_rate: f64,
+    pub command: String,
+}
+
+impl ProcessInfo {
+    fn compare(&self, other: &ProcessInfo, column: SortColumn) -> Ordering {
+        match column {
+            SortColumn::Pid => self.pid.cmp(&other.pid),
+            SortColumn::User => self.user.cmp(&other.user),
+            SortColumn::Cpu => self.cpu_percent.total_cmp(&other.cpu_percent),
+            SortColumn::Memory => self.memory.cmp(&other.memory),
+            SortColumn::DiskRead => self.read_rate.total_cmp(&other.read_rate),
+            SortColumn::DiskWrite => self.write_rate.total_cmp(&other.write_rate),
+            SortColumn::Command => self.command.cmp(&other.command),
+        }
+    }
+
+    fn cell(&self, column: SortColumn) -> String {
+        match column {
+            SortColumn::Pid => self.pid.to_string(),
+            SortColumn::User => self.user.clone(),
+            SortColumn::Cpu => format!("{:.1}", self.cpu_percent),
+            SortColumn::Memory => format_memory(self.memory),
+            SortColumn::DiskRead => format_rate(self.read_rate),
+            SortColumn::DiskWrite => format_rate(self.write_rate),
+            SortColumn::Command => self.command.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ProcessStats {
+    processes: Vec<ProcessInfo>,
+    sort_column: SortColumn,
+    descending: bool,
+    // Index of the first row shown, and how many rows fit
+    scroll: usize,
+    rows: usize,
+    last_refresh: Option<Instant>,
+    // Uids already looked up in passwd, so one without an entry is not retried every tick
+    looked_up_uids: HashSet<Uid>,
+}
+
+impl ProcessStats {
+    pub fn new() -> Self {
+        ProcessStats {
+            processes: Vec::new(),
+            sort_column: SortColumn::Cpu,
+            descending: true,
+            scroll: 0,
+            rows: PROCESS_ROWS,
+            last_refresh: None,
+            looked_up_uids: HashSet::new(),
+        }
+    }
+
+    pub fn update(&mut self, sys_obj: &mut SysWrapper) {
+        // The default refresh leaves user and command line unset for processes started
+        // after launch
+        let refresh_kind = ProcessRefreshKind::nothing()
+            .with_cpu()
+            .with_memory()
+            .with_disk_usage()
+            .with_user(UpdateKind::OnlyIfNotSet)
+            .with_cmd(UpdateKind::OnlyIfNotSet);
+        sys_obj
+            .sys
+            .refresh_processes_specifics(ProcessesToUpdate::All, true, refresh_kind);
+
+        // Disk usage is the bytes moved since the previous refresh, so turn it into a rate
+        let now = Instant::now();
+        let elapsed = self
+            .last_refresh
+            .map(|last| now.duration_since(last).as_secs_f64())
+            .filter(|elapsed| *elapsed > 0.0);
+        self.last_refresh = Some(now);
+
+        // Pick up accounts created since startup
+        let unknown_uids: Vec<Uid> = sys_obj
+            .sys
+            .processes()
+            .values()
+            .filter_map(|process| process.user_id())
+            .filter(|uid| {
+                sys_obj.users.get_user_by_id(uid).is_none() && !self.looked_up_uids.contains(uid)
+            })
+            .cloned()
+            .collect();
+        if !unknown_uids.is_empty() {
+            sys_obj.users.refresh();
+            self.looked_up_uids.extend(unknown_uids);
+        }
+
+        self.processes = sys_obj
+            .sys
+            .processes()
+            .values()
+            // sysinfo lists the threads of each process as well; kernel threads stay
+            .filter(|process| !is_userland_thread(process))
+            .map(|process| {
+                let disk_usage = process.disk_usage();
+                let rate = |bytes: u64| elapsed.map_or(0.0, |elapsed| bytes as f64 / elapsed);
+                ProcessInfo {
+                    pid: process.pid().as_u32(),
+                    user: user_name(&sys_obj.users, process),
+                    cpu_percent: process.cpu_usage() as f64,
+                    memory: process.memory(),
+                    read_rate: rate(disk_usage.read_bytes),
+                    write_rate: rate(disk_usage.written_bytes),
+                    command: command_line(process),
+                }
+            })
+            .collect();
+
+        self.sort();
+        self.scroll = self.scroll.min(self.max_scroll());
+    }
+
+    fn sort(&mut self) {
+        let (column, descending) = (self.sort_column, self.descending);
+        self.processes.sort_by(|a, b| {
+            let ordering = a.compare(b, column).then_with(|| a.pid.cmp(&b.pid));
+            if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+    }
+
+    fn max_scroll(&self) -> usize {
+        self.processes.len().saturating_sub(self.rows)
+    }
+
+    // Forget the previous refresh after a pause, so the next disk rates are not averaged
+    // over the time the table was hidden
+    pub fn reset_rates(&mut self) {
+        self.last_refresh = None;
+    }
+
+    // Fit the table into a panel of this many lines, borders included
+    pub fn set_height(&mut self, height: usize) {
+        self.rows = height.saturating_sub(PANEL_LINES).max(1);
+        self.scroll = self.scroll.min(self.max_scroll());
+    }
+
+    // Rows moved by PgUp/PgDn
+    pub fn page_rows(&self) -> usize {
+        self.rows
+    }
+
+    // Width of the widest row, without borders
+    pub fn width() -> usize {
+        SortColumn::ALL
+            .iter()
+            .map(|column| column.layout().0 + 1)
+            .sum()
+    }
+
+    pub fn scroll_by(&mut self, rows: isize) {
+        self.scroll = self
+            .scroll
+            .saturating_add_signed(rows)
+            .min(self.max_scroll());
+    }
+
+    pub fn scroll_to_top(&mut self) {
+        self.scroll = 0;
+    }
+
+    pub fn scroll_to_bottom(&mut self) {
+        self.scroll = self.max_scroll();
+    }
+
+    // Move the sort column left (-1) or right (+1)
+    pub fn move_sort_column(&mut self, step: isize) {
+        let count = SortColumn::ALL.len() as isize;
+        let index = (self.sort_column.index() as isize + step).rem_euclid(count);
+        self.sort_column = SortColumn::ALL[index as usize];
+        self.descending = self.sort_column.descending_by_default();
+        self.sort();
+    }
+
+    pub fn toggle_sort_order(&mut self) {
+        self.descending = !self.descending;
+        self.sort();
+    }
+
+    pub fn get_mouse(&self) -> Mouse {
+        let last_row = (self.scroll + self.rows).min(self.processes.len());
+        let title = format!(
+            "Processes ({}-{} of {})",
+            (self.scroll + 1).min(last_row),
+            last_row,
+            self.processes.len()
+        );
+        let mut mouse = Mouse::new(title);
+
+        // Header with the sort column highlighted and an arrow for the direction
+        let arrow = if self.descending { "▼" } else { "▲" };
+        let header: Vec<Span<'static>> = SortColumn::ALL
+            .iter()
+            .map(|column| {
+                let mut name = column.header().to_string();
+                if *column == self.sort_column {
+                    name.push_str(arrow);
+                }
+                let text = pad(&name, *column);
+                if *column == self.sort_column {
+                    Span::styled(
+                        text,
+                        Style::default()
+                            .fg(Color::Cyan)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                } else {
+                    Span::styled(text, Style::default().add_modifier(Modifier::BOLD))
+                }
+            })
+            .collect();
+        mouse.add_line(Line::from(header));
+
+        for process in &self.processes[self.scroll.min(last_row)..last_row] {
+            let row: Vec<String> = SortColumn::ALL
+                .iter()
+                .map(|column| pad(&process.cell(*column), *column))
+                .collect();
+            mouse.add(row.join("").trim_end().to_string());
+        }
+
+        mouse
+    }
+}
+
+pub fn format_memory(bytes: u64) -> String {
+    let memory = Byte::from_u64(bytes).get_appropriate_unit(UnitType::Binary);
+    format!("{:.1}{}", memory.get_value(), memory.get_unit())
+}
+
+// Owner of the process, or the bare uid when it has no passwd entry
+pub fn user_name(users: &Users, process: &Process) -> String {
+    process
+        .user_id()
+        .map(|uid| match users.get_user_by_id(uid) {
+            Some(user) => user.name().to_string(),
+            None => uid.to_string(),
+        })
+        .unwrap_or_else(|| String::from("?"))
+}
+
+// Threads of a user process, which sysinfo lists next to the processes themselves
+pub fn is_userland_thread(process: &Process) -> bool {
+    process.thread_kind() == Some(ThreadKind::Userland)
+}
+
+// Fit a cell to its column, with a space in front as separator
+fn pad(text: &str, column: SortColumn) -> String {
+    let (width, right_aligned) = column.layout();
+    let text: String = text.chars().take(width).collect();
+    if right_aligned {
+        format!(" {:>width$}", text, width = width)
+    } else {
+        format!(" {:<width$}", text, width = width)
+    }
+}
+
+// Full command line, or the bracketed name for kernel threads that have none
+fn command_line(process: &Process) -> String {
+    let cmd: Vec<String> = process
+        .cmd()
+        .iter()
+        .map(|arg| arg.to_string_lossy().to_string())
+        .collect();
+    if cmd.is_empty() {
+        format!("[{}]", process.name().to_string_lossy())
+    } else {
+        cmd.join(" ")
+    }
+}
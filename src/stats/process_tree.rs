@@ -0,0 +1,319 @@
+use crate::{
+    common::SysWrapper,
+    renderer::core::Mouse,
+    stats::process::{format_memory, is_userland_thread, user_name},
+};
+use ratatui::{
+    style::{Modifier, Style},
+    text::Line,
+};
+use std::collections::HashMap;
+
+// Rows of the tree shown until set_height sizes it to the terminal; the busiest
+// branches are expanded first
+const TREE_ROWS: usize = 20;
+const USER_ROWS: usize = 8;
+const NAME_WIDTH: usize = 40;
+// Borders, both headers and the blank line between the two tables
+const PANEL_LINES: usize = 5;
+
+struct TreeNode {
+    name: String,
+    cpu_percent: f64,
+    memory: u64,
+    children: Vec<u32>,
+}
+
+// Usage of a process together with all of its descendants
+#[derive(Clone, Copy, Default)]
+struct Subtree {
+    cpu_percent: f64,
+    memory: u64,
+    processes: usize,
+}
+
+#[derive(Clone)]
+pub struct TreeRow {
+    // Branch drawing in front of the name, e.g. "│  ├─ "
+    pub prefix: String,
+    pub pid: u32,
+    pub name: String,
+    pub cpu_percent: f64,
+    pub memory: u64,
+    pub processes: usize,
+}
+
+#[derive(Clone)]
+pub struct UserUsage {
+    pub user: String,
+    pub cpu_percent: f64,
+    pub memory: u64,
+    pub processes: usize,
+}
+
+#[derive(Clone)]
+pub struct ProcessTreeStats {
+    rows: Vec<TreeRow>,
+    users: Vec<UserUsage>,
+    // Lines the panel may take, borders included
+    height: usize,
+}
+
+impl ProcessTreeStats {
+    pub fn new() -> Self {
+        ProcessTreeStats {
+            rows: Vec::new(),
+            users: Vec::new(),
+            height: TREE_ROWS + USER_ROWS + PANEL_LINES,
+        }
+    }
+
+    // Fit the panel into this many lines, borders included; takes effect on the next update
+    pub fn set_height(&mut self, height: usize) {
+        self.height = height;
+    }
+
+    // Width of the widest row, without borders
+    pub fn width() -> usize {
+        NAME_WIDTH + 25
+    }
+
+    // Reads the process list as left by ProcessStats::update, which does the refresh
+    pub fn update(&mut self, sys_obj: &mut SysWrapper) {
+        let mut nodes: HashMap<u32, TreeNode> = HashMap::new();
+        let mut parents: HashMap<u32, u32> = HashMap::new();
+        let mut users: HashMap<String, UserUsage> = HashMap::new();
+
+        for process in sys_obj.sys.processes().values() {
+            // Same rows as the process table, so kernel threads hang under kthreadd
+            if is_userland_thread(process) {
+                continue;
+            }
+            let pid = process.pid().as_u32();
+            let cpu_percent = process.cpu_usage() as f64;
+            nodes.insert(
+                pid,
+                TreeNode {
+                    name: process.name().to_string_lossy().to_string(),
+                    cpu_percent,
+                    memory: process.memory(),
+                    children: Vec::new(),
+                },
+            );
+            if let Some(parent) = process.parent() {
+                parents.insert(pid, parent.as_u32());
+            }
+
+            let user = user_name(&sys_obj.users, process);
+            let usage = users.entry(user.clone()).or_insert(UserUsage {
+                user,
+                cpu_percent: 0.0,
+                memory: 0,
+                processes: 0,
+            });
+            usage.cpu_percent += cpu_percent;
+            usage.memory += process.memory();
+            usage.processes += 1;
+        }
+
+        // Processes whose parent is gone (or not visible) become roots
+        let mut roots: Vec<u32> = Vec::new();
+        for pid in nodes.keys().copied().collect::<Vec<u32>>() {
+            match parents.get(&pid) {
+                Some(parent) if nodes.contains_key(parent) => {
+                    if let Some(node) = nodes.get_mut(parent) {
+                        node.children.push(pid);
+                    }
+                }
+                _ => roots.push(pid),
+            }
+        }
+
+        let mut subtrees: HashMap<u32, Subtree> = HashMap::new();
+        for root in &roots {
+            sum_subtree(*root, &nodes, &mut subtrees);
+        }
+
+        // A stale or reused ppid in the /proc snapshot can close a loop (A -> B -> A) that
+        // no root leads to; cut it at its lowest pid and make that a root
+        let mut unreached: Vec<u32> = nodes
+            .keys()
+            .filter(|pid| !subtrees.contains_key(pid))
+            .copied()
+            .collect();
+        unreached.sort();
+        for pid in unreached {
+            if subtrees.contains_key(&pid) {
+                continue;
+            }
+            if let Some(parent) = parents.get(&pid).and_then(|parent| nodes.get_mut(parent)) {
+                parent.children.retain(|child| *child != pid);
+            }
+            roots.push(pid);
+            sum_subtree(pid, &nodes, &mut subtrees);
+        }
+
+        // Busiest first; memory and then pid keep the order stable when idle
+        let order = |pids: &mut Vec<u32>| {
+            pids.sort_by(|a, b| {
+                let subtree = |pid| subtrees.get(pid).copied().unwrap_or_default();
+                let (sa, sb): (Subtree, Subtree) = (subtree(a), subtree(b));
+                sb.cpu_percent
+                    .total_cmp(&sa.cpu_percent)
+                    .then(sb.memory.cmp(&sa.memory))
+                    .then(a.cmp(b))
+            })
+        };
+        order(&mut roots);
+        for node in nodes.values_mut() {
+            order(&mut node.children);
+        }
+
+        // Users get up to a third of the lines and the tree the rest
+        let lines = self.height.saturating_sub(PANEL_LINES);
+        self.users = users.into_values().collect();
+        self.users.sort_by(|a, b| {
+            b.cpu_percent
+                .total_cmp(&a.cpu_percent)
+                .then(b.memory.cmp(&a.memory))
+                .then(a.user.cmp(&b.user))
+        });
+        self.users.truncate(USER_ROWS.min(lines / 3).max(1));
+        let tree_rows = lines.saturating_sub(self.users.len()).max(1);
+
+        self.rows.clear();
+        for (i, root) in roots.iter().enumerate() {
+            let last = i + 1 == roots.len();
+            self.add_rows(*root, "", "", last, true, tree_rows, &nodes, &subtrees);
+        }
+    }
+
+    // Depth-first walk that stops once the panel is full
+    #[allow(clippy::too_many_arguments)]
+    fn add_rows(
+        &mut self,
+        pid: u32,
+        indent: &str,
+        branch: &str,
+        last: bool,
+        root: bool,
+        limit: usize,
+        nodes: &HashMap<u32, TreeNode>,
+        subtrees: &HashMap<u32, Subtree>,
+    ) {
+        if self.rows.len() >= limit {
+            return;
+        }
+        let node = &nodes[&pid];
+        let subtree = subtrees.get(&pid).copied().unwrap_or_default();
+        self.rows.push(TreeRow {
+            prefix: format!("{}{}", indent, branch),
+            pid,
+            name: node.name.clone(),
+            cpu_percent: subtree.cpu_percent,
+            memory: subtree.memory,
+            processes: subtree.processes,
+        });
+
+        let child_indent = if root {
+            String::new()
+        } else if last {
+            format!("{}   ", indent)
+        } else {
+            format!("{}│  ", indent)
+        };
+        for (i, child) in node.children.iter().enumerate() {
+            let last_child = i + 1 == node.children.len();
+            let branch = if last_child { "└─ " } else { "├─ " };
+            self.add_rows(
+                *child,
+                &child_indent,
+                branch,
+                last_child,
+                false,
+                limit,
+                nodes,
+                subtrees,
+            );
+        }
+    }
+
+    pub fn get_mouse(&self) -> Mouse {
+        let mut mouse = Mouse::new(String::from("Process Tree"));
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+
+        mouse.add_line(Line::styled(
+            format!(
+                "{:<width$} {:>6} {:>10} {:>6}",
+                "USER",
+                "CPU%",
+                "MEM",
+                "PROCS",
+                width = NAME_WIDTH
+            ),
+            bold,
+        ));
+        for usage in &self.users {
+            mouse.add(format!(
+                "{:<width$} {:>6.1} {:>10} {:>6}",
+                truncate(&usage.user, NAME_WIDTH),
+                usage.cpu_percent,
+                format_memory(usage.memory),
+                usage.processes,
+                width = NAME_WIDTH
+            ));
+        }
+
+        // CPU and memory below include every descendant of the process
+        mouse.add(String::new());
+        mouse.add_line(Line::styled(
+            format!(
+                "{:<width$} {:>6} {:>10} {:>6}",
+                "PROCESS (incl. children)",
+                "CPU%",
+                "MEM",
+                "PROCS",
+                width = NAME_WIDTH
+            ),
+            bold,
+        ));
+        for row in &self.rows {
+            let label = format!("{}{} ({})", row.prefix, row.name, row.pid);
+            mouse.add(format!(
+                "{:<width$} {:>6.1} {:>10} {:>6}",
+                truncate(&label, NAME_WIDTH),
+                row.cpu_percent,
+                format_memory(row.memory),
+                row.processes,
+                width = NAME_WIDTH
+            ));
+        }
+
+        mouse
+    }
+}
+
+fn sum_subtree(
+    pid: u32,
+    nodes: &HashMap<u32, TreeNode>,
+    subtrees: &mut HashMap<u32, Subtree>,
+) -> Subtree {
+    let node = &nodes[&pid];
+    let mut subtree = Subtree {
+        cpu_percent: node.cpu_percent,
+        memory: node.memory,
+        processes: 1,
+    };
+    for child in &node.children {
+        let child = sum_subtree(*child, nodes, subtrees);
+        subtree.cpu_percent += child.cpu_percent;
+        subtree.memory += child.memory;
+        subtree.processes += child.processes;
+    }
+    subtrees.insert(pid, subtree);
+    subtree
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    text.chars().take(width).collect()
+}
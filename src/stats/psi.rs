@@ -0,0 +1,158 @@
+use crate::{
+    common::{read_file_to_string, SysWrapper},
+    renderer::core::Mouse,
+};
+use std::path::Path;
+
+const PSI_RESOURCES: [&str; 3] = ["cpu", "memory", "io"];
+
+#[derive(Clone)]
+pub struct PsiStats {
+    resources: Vec<PsiResource>,
+    // False on kernels without CONFIG_PSI or booted with psi=0
+    available: bool,
+}
+
+#[derive(Clone)]
+struct PsiResource {
+    name: &'static str,
+    some: Option<PsiLine>,
+    full: Option<PsiLine>,
+}
+
+#[derive(Clone, Copy)]
+struct PsiLine {
+    avg10: f64,
+    avg60: f64,
+    avg300: f64,
+    min_avg10: f64,
+    max_avg10: f64,
+}
+
+impl PsiLine {
+    fn update_min_max(&mut self, previous: Option<PsiLine>) {
+        // No previous line means this is the first sample; avg10 is often exactly 0 when
+        // idle, so zero cannot double as "unset"
+        match previous {
+            Some(previous) => {
+                self.min_avg10 = previous.min_avg10.min(self.avg10);
+                self.max_avg10 = previous.max_avg10.max(self.avg10);
+            }
+            None => {
+                self.min_avg10 = self.avg10;
+                self.max_avg10 = self.avg10;
+            }
+        }
+    }
+}
+
+impl PsiStats {
+    pub fn new() -> Self {
+        PsiStats {
+            resources: PSI_RESOURCES
+                .iter()
+                .map(|name| PsiResource {
+                    name,
+                    some: None,
+                    full: None,
+                })
+                .collect(),
+            available: false,
+        }
+    }
+
+    pub fn update(&mut self, _sys_obj: &mut SysWrapper) {
+        self.available = false;
+
+        for resource in &mut self.resources {
+            let path = Path::new("/proc/pressure").join(resource.name);
+            let Ok(contents) = read_file_to_string(&path) else {
+                resource.some = None;
+                resource.full = None;
+                continue;
+            };
+            self.available = true;
+
+            let (mut some, mut full) = parse_pressure(&contents);
+            if let Some(line) = some.as_mut() {
+                line.update_min_max(resource.some);
+            }
+            if let Some(line) = full.as_mut() {
+                line.update_min_max(resource.full);
+            }
+            resource.some = some;
+            resource.full = full;
+        }
+    }
+
+    pub fn get_mouse(&self) -> Mouse {
+        let title = String::from("Pressure");
+        let mut mouse = Mouse::new(title);
+
+        if !self.available {
+            mouse.add(String::from("PSI unavailable"));
+            mouse.add(String::from("(needs CONFIG_PSI and no psi=0)"));
+            return mouse;
+        }
+
+        mouse.add(String::from("avg10/avg60/avg300 (%)"));
+        for resource in &self.resources {
+            for (kind, line) in [("some", resource.some), ("full", resource.full)] {
+                if let Some(line) = line {
+                    mouse.add(format!(
+                        "{} {}: {:.2}/{:.2}/{:.2} (Min: {:.2}, Max: {:.2})",
+                        resource.name,
+                        kind,
+                        line.avg10,
+                        line.avg60,
+                        line.avg300,
+                        line.min_avg10,
+                        line.max_avg10
+                    ));
+                }
+            }
+        }
+
+        mouse
+    }
+}
+
+// Parse a /proc/pressure file, which looks like:
+// "some avg10=0.00 avg60=0.00 avg300=0.00 total=0"
+// "full avg10=0.00 avg60=0.00 avg300=0.00 total=0"
+fn parse_pressure(contents: &str) -> (Option<PsiLine>, Option<PsiLine>) {
+    let mut some = None;
+    let mut full = None;
+
+    for line in contents.lines() {
+        let mut fields = line.split_whitespace();
+        let kind = fields.next();
+
+        let mut psi = PsiLine {
+            avg10: 0.0,
+            avg60: 0.0,
+            avg300: 0.0,
+            min_avg10: 0.0,
+            max_avg10: 0.0,
+        };
+        for field in fields {
+            if let Some((key, value)) = field.split_once('=') {
+                let value = value.parse::<f64>().unwrap_or_default();
+                match key {
+                    "avg10" => psi.avg10 = value,
+                    "avg60" => psi.avg60 = value,
+                    "avg300" => psi.avg300 = value,
+                    _ => {}
+                }
+            }
+        }
+
+        match kind {
+            Some("some") => some = Some(psi),
+            Some("full") => full = Some(psi),
+            _ => {}
+        }
+    }
+
+    (some, full)
+}
@@ -0,0 +1,255 @@
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::process::Command;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::time::{Duration, Instant};
+
+// SMART data barely changes and smartctl is slow, so only query every few minutes
+const SMART_INTERVAL: Duration = Duration::from_secs(300);
+
+#[derive(Clone)]
+pub struct SmartInfo {
+    pub passed: Option<bool>,
+    pub power_on_hours: Option<u64>,
+    // ATA attribute 5 (Reallocated_Sector_Ct)
+    pub reallocated_sectors: Option<u64>,
+    // NVMe health log
+    pub media_errors: Option<u64>,
+    pub percentage_used: Option<u64>,
+}
+
+// What smartctl said about one disk
+enum SmartReading {
+    Info(SmartInfo),
+    // Skipped by "-n standby" so the disk is not spun up
+    Asleep,
+    Unavailable,
+}
+
+// Everything one smartctl pass over the disks found
+struct SmartReport {
+    results: HashMap<String, SmartInfo>,
+    asleep: Vec<String>,
+    // Why SMART data is missing, e.g. smartctl not installed or not running as root
+    notice: Option<String>,
+}
+
+// Cached smartctl results per block device
+pub struct SmartCache {
+    results: HashMap<String, SmartInfo>,
+    notice: Option<String>,
+    last_query: Option<Instant>,
+    // smartctl takes seconds per disk, so passes run on a worker thread
+    pending: Option<Receiver<SmartReport>>,
+}
+
+impl SmartCache {
+    pub fn new() -> Self {
+        SmartCache {
+            results: HashMap::new(),
+            notice: None,
+            last_query: None,
+            pending: None,
+        }
+    }
+
+    // Pick up a finished smartctl pass and start a new one once the interval has passed
+    pub fn refresh(&mut self, devices: &[String]) {
+        if let Some(pending) = &self.pending {
+            match pending.try_recv() {
+                Ok(report) => {
+                    // Sleeping disks keep their last known status; disks that are gone
+                    // or could not be read are dropped
+                    let mut results = report.results;
+                    for device in report.asleep {
+                        if let Some(info) = self.results.remove(&device) {
+                            results.insert(device, info);
+                        }
+                    }
+                    self.results = results;
+                    self.notice = report.notice;
+                    self.pending = None;
+                }
+                Err(TryRecvError::Empty) => return,
+                Err(TryRecvError::Disconnected) => self.pending = None,
+            }
+        }
+
+        if self
+            .last_query
+            .is_some_and(|last| last.elapsed() < SMART_INTERVAL)
+        {
+            return;
+        }
+        self.last_query = Some(Instant::now());
+
+        let (sender, receiver) = mpsc::channel();
+        let devices = devices.to_vec();
+        std::thread::spawn(move || {
+            // The receiver is gone if the UI has exited; nothing left to do then
+            let _ = sender.send(query_devices(&devices));
+        });
+        self.pending = Some(receiver);
+    }
+
+    pub fn get(&self, device: &str) -> Option<&SmartInfo> {
+        self.results.get(device)
+    }
+
+    pub fn notice(&self) -> Option<&str> {
+        self.notice.as_deref()
+    }
+}
+
+fn query_devices(devices: &[String]) -> SmartReport {
+    let mut report = SmartReport {
+        results: HashMap::new(),
+        asleep: Vec::new(),
+        notice: None,
+    };
+    for device in devices {
+        match query_smartctl(device) {
+            Ok(SmartReading::Info(info)) => {
+                report.results.insert(device.clone(), info);
+            }
+            Ok(SmartReading::Asleep) => report.asleep.push(device.clone()),
+            Ok(SmartReading::Unavailable) => {}
+            Err(notice) => report.notice = Some(notice),
+        }
+    }
+    report
+}
+
+// Err carries a message for the user when smartctl cannot be used at all
+fn query_smartctl(device: &str) -> Result<SmartReading, String> {
+    // "-n standby" keeps smartctl from spinning up sleeping disks
+    let output = match Command::new("smartctl")
+        .args(["--json", "-a", "-n", "standby", &format!("/dev/{}", device)])
+        .output()
+    {
+        Ok(output) => output,
+        Err(err) if err.kind() == ErrorKind::NotFound => {
+            return Err(String::from(
+                "SMART: smartctl not found (install smartmontools)",
+            ));
+        }
+        Err(err) => return Err(format!("SMART: smartctl failed: {}", err)),
+    };
+
+    // smartctl's exit status is a bitmask that is non-zero for any logged error, so
+    // parse whatever JSON it printed rather than trusting the status
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if stdout.contains("Permission denied") {
+        return Err(String::from("SMART: permission denied (run as root)"));
+    }
+    if is_asleep(&stdout) {
+        return Ok(SmartReading::Asleep);
+    }
+    Ok(parse_smartctl_json(&stdout).map_or(SmartReading::Unavailable, SmartReading::Info))
+}
+
+// Whether smartctl skipped the disk because it is in standby or sleep mode
+pub fn is_asleep(json: &str) -> bool {
+    let Ok(root) = serde_json::from_str::<Value>(json) else {
+        return false;
+    };
+    root["smartctl"]["messages"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|message| message["string"].as_str())
+        .any(|message| message.starts_with("Device is in ") && message.contains(" mode"))
+}
+
+// Parse the output of `smartctl --json -a`. Returns None if smartctl could not open the
+// device (not root, disk in standby, unsupported bridge) so nothing stale is shown
+pub fn parse_smartctl_json(json: &str) -> Option<SmartInfo> {
+    let root: Value = serde_json::from_str(json).ok()?;
+
+    let passed = root["smart_status"]["passed"].as_bool();
+    let power_on_hours = root["power_on_time"]["hours"].as_u64();
+
+    let reallocated_sectors = root["ata_smart_attributes"]["table"]
+        .as_array()
+        .and_then(|table| {
+            table
+                .iter()
+                .find(|attribute| attribute["id"].as_u64() == Some(5))
+        })
+        .and_then(|attribute| attribute["raw"]["value"].as_u64());
+
+    let nvme_log = &root["nvme_smart_health_information_log"];
+    let media_errors = nvme_log["media_errors"].as_u64();
+    let percentage_used = nvme_log["percentage_used"].as_u64();
+
+    if passed.is_none() && power_on_hours.is_none() {
+        return None;
+    }
+
+    Some(SmartInfo {
+        passed,
+        power_on_hours,
+        reallocated_sectors,
+        media_errors,
+        percentage_used,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_ata_attributes() {
+        let info = parse_smartctl_json(include_str!("../../tests/fixtures/smartctl/ata.json"))
+            .expect("ATA fixture should parse");
+        assert_eq!(info.passed, Some(true));
+        assert_eq!(info.power_on_hours, Some(21345));
+        assert_eq!(info.reallocated_sectors, Some(3));
+        assert_eq!(info.media_errors, None);
+        assert_eq!(info.percentage_used, None);
+    }
+
+    #[test]
+    fn parses_nvme_health_log() {
+        let info = parse_smartctl_json(include_str!("../../tests/fixtures/smartctl/nvme.json"))
+            .expect("NVMe fixture should parse");
+        assert_eq!(info.passed, Some(false));
+        assert_eq!(info.power_on_hours, Some(8760));
+        assert_eq!(info.reallocated_sectors, None);
+        assert_eq!(info.media_errors, Some(12));
+        assert_eq!(info.percentage_used, Some(7));
+    }
+
+    #[test]
+    fn leaves_missing_attributes_empty() {
+        let info = parse_smartctl_json(include_str!(
+            "../../tests/fixtures/smartctl/missing_attributes.json"
+        ))
+        .expect("a passed status alone is enough to report");
+        assert_eq!(info.passed, Some(true));
+        assert_eq!(info.power_on_hours, None);
+        assert_eq!(info.reallocated_sectors, None);
+        assert_eq!(info.media_errors, None);
+        assert_eq!(info.percentage_used, None);
+    }
+
+    #[test]
+    fn rejects_unopened_device() {
+        let json = include_str!("../../tests/fixtures/smartctl/permission_denied.json");
+        assert!(parse_smartctl_json(json).is_none());
+        assert!(!is_asleep(json));
+        assert!(parse_smartctl_json("not json").is_none());
+    }
+
+    #[test]
+    fn recognises_disk_in_standby() {
+        let json = include_str!("../../tests/fixtures/smartctl/standby.json");
+        assert!(is_asleep(json));
+        assert!(parse_smartctl_json(json).is_none());
+        assert!(!is_asleep(include_str!(
+            "../../tests/fixtures/smartctl/ata.json"
+        )));
+    }
+}
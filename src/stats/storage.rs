@@ -0,0 +1,226 @@
+use crate::{
+    common::{read_file_to_string, SysWrapper},
+    renderer::core::Mouse,
+    stats::{
+        hwmon::{HwmonChip, Sensor, SensorKind},
+        smart::{SmartCache, SmartInfo},
+    },
+};
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+};
+use std::path::Path;
+
+pub struct StorageStats {
+    drives: Vec<DriveInfo>,
+    smart: SmartCache,
+}
+
+#[derive(Clone)]
+pub struct DriveInfo {
+    // Block device name, e.g. "nvme0n1" or "sda"
+    pub block: String,
+    pub model: String,
+    pub driver: String,
+    // Composite plus any extra sensors; min/max come from the hwmon session tracking
+    pub sensors: Vec<Sensor>,
+    pub smart: Option<SmartInfo>,
+}
+
+impl StorageStats {
+    pub fn new() -> Self {
+        StorageStats {
+            drives: Vec::new(),
+            smart: SmartCache::new(),
+        }
+    }
+
+    pub fn update(&mut self, sys_obj: &mut SysWrapper) {
+        self.drives.clear();
+
+        for chip in sys_obj.hwmon.chips() {
+            // nvme covers NVMe SSDs, drivetemp covers SATA disks
+            if chip.name != "nvme" && chip.name != "drivetemp" {
+                continue;
+            }
+
+            let block = block_device_name(chip).unwrap_or_else(|| chip.hwmon.clone());
+            self.drives.push(DriveInfo {
+                model: read_model(&block),
+                block,
+                driver: chip.name.clone(),
+                sensors: chip.sensors_of(SensorKind::Temperature).cloned().collect(),
+                smart: None,
+            });
+        }
+
+        // SMART covers every physical disk, including ones without a temperature sensor
+        let disks = physical_disks();
+        self.smart.refresh(&disks);
+        for disk in disks {
+            let smart = self.smart.get(&disk).cloned();
+            match self.drives.iter_mut().find(|drive| drive.block == disk) {
+                Some(drive) => drive.smart = smart,
+                None if smart.is_some() => self.drives.push(DriveInfo {
+                    model: read_model(&disk),
+                    block: disk,
+                    driver: String::from("smart"),
+                    sensors: Vec::new(),
+                    smart,
+                }),
+                None => {}
+            }
+        }
+
+        self.drives.sort_by(|a, b| a.block.cmp(&b.block));
+    }
+
+    pub fn get_mouse(&self) -> Mouse {
+        let title = String::from("Storage");
+        let mut mouse = Mouse::new(title);
+
+        if self.drives.is_empty() {
+            mouse.add(String::from("No drive temperature sensors"));
+            mouse.add(String::from("(SATA needs the drivetemp module)"));
+        }
+
+        for (i, drive) in self.drives.iter().enumerate() {
+            if i > 0 {
+                mouse.add(String::from("")); // Add empty line between drives
+            }
+
+            if drive.model.is_empty() {
+                mouse.add(format!("{} ({})", drive.block, drive.driver));
+            } else {
+                mouse.add(format!("{}: {}", drive.block, drive.model));
+            }
+
+            for sensor in &drive.sensors {
+                // drivetemp exposes a single unlabelled channel
+                let label = if sensor.label == "temp1" {
+                    "Drive"
+                } else {
+                    &sensor.label
+                };
+                let mut line = format!(
+                    "  {}: {:.1}°C (Min: {:.1}°C, Max: {:.1}°C)",
+                    label, sensor.value, sensor.min_value, sensor.max_value
+                );
+                if let Some(crit) = sensor.crit_limit {
+                    line.push_str(&format!(" Crit: {:.1}°C", crit));
+                }
+                if sensor.max_limit.is_some_and(|max| sensor.value >= max)
+                    || sensor.crit_limit.is_some_and(|crit| sensor.value >= crit)
+                {
+                    line.push_str(" [HOT]");
+                }
+                mouse.add(line);
+            }
+
+            if let Some(smart) = &drive.smart {
+                mouse.add_line(smart_line(smart));
+            }
+        }
+
+        if let Some(notice) = self.smart.notice() {
+            if !self.drives.is_empty() {
+                mouse.add(String::from(""));
+            }
+            mouse.add_line(Line::styled(
+                notice.to_string(),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+
+        mouse
+    }
+}
+
+fn smart_line(smart: &SmartInfo) -> Line<'static> {
+    let status = match smart.passed {
+        Some(true) => Span::styled("PASSED", Style::default().fg(Color::Green)),
+        Some(false) => Span::styled("FAILED", Style::default().fg(Color::Red)),
+        None => Span::raw("Unknown"),
+    };
+
+    let mut details = Vec::new();
+    if let Some(hours) = smart.power_on_hours {
+        details.push(format!("Power On: {}h", hours));
+    }
+    if let Some(sectors) = smart.reallocated_sectors {
+        details.push(format!("Reallocated: {}", sectors));
+    }
+    if let Some(errors) = smart.media_errors {
+        details.push(format!("Media Errors: {}", errors));
+    }
+    if let Some(used) = smart.percentage_used {
+        details.push(format!("Used: {}%", used));
+    }
+
+    let mut spans = vec![Span::raw("  SMART: "), status];
+    if !details.is_empty() {
+        spans.push(Span::raw(format!(", {}", details.join(", "))));
+    }
+    Line::from(spans)
+}
+
+fn read_model(block: &str) -> String {
+    read_file_to_string(&Path::new("/sys/block").join(block).join("device/model"))
+        .map(|model| model.trim().to_string())
+        .unwrap_or_default()
+}
+
+// Whole disks backed by real hardware (skips loop, zram, device-mapper, md, ...)
+fn physical_disks() -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir("/sys/block") else {
+        return Vec::new();
+    };
+
+    let mut disks: Vec<String> = entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().join("device").exists())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| !name.starts_with("zram") && !name.starts_with("loop"))
+        .collect();
+    disks.sort();
+    disks
+}
+
+// Follow the hwmon device link back to the block device it reports on
+fn block_device_name(chip: &HwmonChip) -> Option<String> {
+    let device = chip.device_path()?;
+
+    if chip.name == "drivetemp" {
+        // SCSI device directory, e.g. .../0:0:0:0/block/sda
+        return first_entry(&device.join("block"), |_| true);
+    }
+
+    // Newer kernels attach nvme hwmon to the controller (nvme0), older ones to the PCI
+    // function that contains nvme/nvme0
+    let controller = if device
+        .file_name()
+        .is_some_and(|name| name.to_string_lossy().starts_with("nvme"))
+    {
+        device
+    } else {
+        let name = first_entry(&device.join("nvme"), |_| true)?;
+        device.join("nvme").join(name)
+    };
+    let controller_name = controller.file_name()?.to_string_lossy().to_string();
+
+    // Namespaces are nvme0n1, nvme0n2, ...; hidden multipath paths look like nvme0c0n1
+    let namespace_prefix = format!("{}n", controller_name);
+    first_entry(&controller, |name| name.starts_with(&namespace_prefix)).or(Some(controller_name))
+}
+
+fn first_entry(dir: &Path, filter: impl Fn(&str) -> bool) -> Option<String> {
+    let mut names: Vec<String> = std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(Result::ok)
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| filter(name))
+        .collect();
+    names.sort();
+    names.into_iter().next()
+}
@@ -0,0 +1,264 @@
+use crate::{
+    common::{read_file_to_string, SysWrapper},
+    renderer::core::Mouse,
+};
+use std::path::Path;
+
+const THERMAL_PATH: &str = "/sys/class/thermal";
+
+#[derive(Clone)]
+pub struct ThermalStats {
+    zones: Vec<ThermalZone>,
+    // Cooling devices not bound to any zone
+    unbound_cooling: Vec<CoolingDevice>,
+}
+
+#[derive(Clone)]
+pub struct ThermalZone {
+    pub name: String,
+    pub zone_type: String,
+    pub temp: f64,
+    pub min_temp: f64,
+    pub max_temp: f64,
+    pub trip_points: Vec<TripPoint>,
+    pub cooling: Vec<CoolingDevice>,
+}
+
+#[derive(Clone)]
+pub struct TripPoint {
+    // passive, active, hot or critical
+    pub trip_type: String,
+    pub temp: f64,
+}
+
+#[derive(Clone, PartialEq)]
+pub struct CoolingDevice {
+    pub name: String,
+    pub device_type: String,
+    pub cur_state: u64,
+    pub max_state: u64,
+}
+
+impl ThermalZone {
+    // The lowest trip point the zone has not reached yet
+    pub fn next_trip(&self) -> Option<&TripPoint> {
+        self.trip_points
+            .iter()
+            .filter(|trip| trip.temp > self.temp)
+            .min_by(|a, b| a.temp.total_cmp(&b.temp))
+    }
+}
+
+impl ThermalStats {
+    pub fn new() -> Self {
+        ThermalStats {
+            zones: Vec::new(),
+            unbound_cooling: Vec::new(),
+        }
+    }
+
+    pub fn update(&mut self, _sys_obj: &mut SysWrapper) {
+        let thermal_path = Path::new(THERMAL_PATH);
+        let mut names: Vec<String> = match std::fs::read_dir(thermal_path) {
+            Ok(entries) => entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+        names.sort_by_key(|name| trailing_number(name));
+
+        let mut cooling_devices: Vec<CoolingDevice> = names
+            .iter()
+            .filter(|name| name.starts_with("cooling_device"))
+            .filter_map(|name| read_cooling_device(&thermal_path.join(name), name))
+            .collect();
+
+        let mut zones = Vec::new();
+        for name in names.iter().filter(|name| name.starts_with("thermal_zone")) {
+            let zone_path = thermal_path.join(name);
+            // Disabled zones or ones whose sensor is gone fail to read temp
+            let Some(temp) = read_number(&zone_path.join("temp")) else {
+                continue;
+            };
+            let temp = temp / 1000.0;
+
+            let (min_temp, max_temp) = match self.zones.iter().find(|z| &z.name == name) {
+                Some(previous) => (previous.min_temp.min(temp), previous.max_temp.max(temp)),
+                None => (temp, temp),
+            };
+
+            let mut zone = ThermalZone {
+                name: name.clone(),
+                zone_type: read_trimmed(&zone_path.join("type")),
+                temp,
+                min_temp,
+                max_temp,
+                trip_points: Vec::new(),
+                cooling: Vec::new(),
+            };
+
+            if let Ok(entries) = std::fs::read_dir(&zone_path) {
+                let mut files: Vec<String> = entries
+                    .filter_map(Result::ok)
+                    .map(|entry| entry.file_name().to_string_lossy().to_string())
+                    .collect();
+                files.sort_by_key(|file| trailing_number(file.trim_end_matches("_type")));
+
+                for file in &files {
+                    // trip_point_N_type / trip_point_N_temp
+                    if let Some(index) = file
+                        .strip_prefix("trip_point_")
+                        .and_then(|rest| rest.strip_suffix("_type"))
+                    {
+                        let trip_temp =
+                            read_number(&zone_path.join(format!("trip_point_{}_temp", index)));
+                        // Disabled trips read as a huge or negative value
+                        if let Some(trip_temp) = trip_temp.filter(|t| *t > 0.0 && *t < 1_000_000.0)
+                        {
+                            zone.trip_points.push(TripPoint {
+                                trip_type: read_trimmed(&zone_path.join(file)),
+                                temp: trip_temp / 1000.0,
+                            });
+                        }
+                    }
+
+                    // cdevN links point at the cooling devices bound to this zone
+                    if file.starts_with("cdev") && !file.contains('_') {
+                        if let Ok(target) = std::fs::read_link(zone_path.join(file)) {
+                            let device = target.file_name().unwrap_or_default().to_string_lossy();
+                            if let Some(cooling) = cooling_devices.iter().find(|c| c.name == device)
+                            {
+                                if !zone.cooling.contains(cooling) {
+                                    zone.cooling.push(cooling.clone());
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            zones.push(zone);
+        }
+
+        cooling_devices.retain(|cooling| !zones.iter().any(|zone| zone.cooling.contains(cooling)));
+        self.unbound_cooling = cooling_devices;
+        self.zones = zones;
+    }
+
+    pub fn get_mouse(&self) -> Mouse {
+        let title = String::from("Thermal Zones");
+        let mut mouse = Mouse::new(title);
+
+        if self.zones.is_empty() {
+            mouse.add(String::from("No thermal zones detected"));
+            return mouse;
+        }
+
+        for (i, zone) in self.zones.iter().enumerate() {
+            if i > 0 {
+                mouse.add(String::from("")); // Add empty line between zones
+            }
+
+            mouse.add(format!(
+                "{} ({}): {:.1}°C (Min: {:.1}°C, Max: {:.1}°C)",
+                zone.zone_type, zone.name, zone.temp, zone.min_temp, zone.max_temp
+            ));
+
+            if !zone.trip_points.is_empty() {
+                let trips: Vec<String> = zone
+                    .trip_points
+                    .iter()
+                    .map(|trip| format!("{} {:.1}°C", trip.trip_type, trip.temp))
+                    .collect();
+                mouse.add(format!("  Trips: {}", trips.join(", ")));
+            }
+
+            if let Some(trip) = zone.next_trip() {
+                mouse.add(format!(
+                    "  Next: {} in {:.1}°C",
+                    trip.trip_type,
+                    trip.temp - zone.temp
+                ));
+            }
+
+            for line in cooling_lines(&zone.cooling) {
+                mouse.add(line);
+            }
+        }
+
+        if !self.unbound_cooling.is_empty() {
+            mouse.add(String::from(""));
+            mouse.add(String::from("Cooling devices:"));
+            for line in cooling_lines(&self.unbound_cooling) {
+                mouse.add(line);
+            }
+        }
+
+        mouse
+    }
+}
+
+// One line per cooling device type, so the per-CPU "Processor" devices on a large machine
+// collapse into a single "Processor (x64): 0-2/10" line
+fn cooling_lines(devices: &[CoolingDevice]) -> Vec<String> {
+    let mut types: Vec<&str> = Vec::new();
+    for device in devices {
+        if !types.contains(&device.device_type.as_str()) {
+            types.push(&device.device_type);
+        }
+    }
+
+    types
+        .iter()
+        .map(|device_type| {
+            let group: Vec<&CoolingDevice> = devices
+                .iter()
+                .filter(|device| device.device_type == *device_type)
+                .collect();
+            let low = group.iter().map(|d| d.cur_state).min().unwrap_or_default();
+            let high = group.iter().map(|d| d.cur_state).max().unwrap_or_default();
+            let max_state = group.iter().map(|d| d.max_state).max().unwrap_or_default();
+
+            let name = if group.len() > 1 {
+                format!("{} (x{})", device_type, group.len())
+            } else {
+                device_type.to_string()
+            };
+            let state = if low == high {
+                low.to_string()
+            } else {
+                format!("{}-{}", low, high)
+            };
+            format!("  {}: {}/{}", name, state, max_state)
+        })
+        .collect()
+}
+
+fn read_cooling_device(path: &Path, name: &str) -> Option<CoolingDevice> {
+    Some(CoolingDevice {
+        name: name.to_string(),
+        device_type: read_trimmed(&path.join("type")),
+        cur_state: read_number(&path.join("cur_state"))? as u64,
+        max_state: read_number(&path.join("max_state"))? as u64,
+    })
+}
+
+fn read_number(path: &Path) -> Option<f64> {
+    read_file_to_string(path).ok()?.trim().parse::<f64>().ok()
+}
+
+fn read_trimmed(path: &Path) -> String {
+    read_file_to_string(path)
+        .map(|value| value.trim().to_string())
+        .unwrap_or_default()
+}
+
+// Sort key for names like "thermal_zone10" so they order numerically
+fn trailing_number(name: &str) -> (String, u32) {
+    let digits_at = name
+        .rfind(|c: char| !c.is_ascii_digit())
+        .map_or(0, |i| i + 1);
+    let (prefix, number) = name.split_at(digits_at);
+    (prefix.to_string(), number.parse::<u32>().unwrap_or(0))
+}
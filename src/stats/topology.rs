@@ -0,0 +1,149 @@
+use crate::common::read_file_to_string;
+use std::path::Path;
+
+const CPU_PATH: &str = "/sys/devices/system/cpu";
+
+#[derive(Clone)]
+pub struct LogicalCpu {
+    pub id: usize,
+    pub package_id: i32,
+    pub die_id: i32,
+    pub cluster_id: i32,
+    pub core_id: i32,
+}
+
+// A physical core and the logical CPUs (hyperthreads) that run on it
+#[derive(Clone)]
+pub struct PhysicalCore {
+    pub package_id: i32,
+    pub die_id: i32,
+    pub cluster_id: i32,
+    pub core_id: i32,
+    pub threads: Vec<usize>,
+}
+
+#[derive(Clone)]
+pub struct CpuTopology {
+    pub cpus: Vec<LogicalCpu>,
+    pub cores: Vec<PhysicalCore>,
+    // None when the kernel has no SMT control (e.g. most ARM boards)
+    pub smt_active: Option<bool>,
+    // Online CPU mask (e.g. "0-7,9") the topology was read for, None before the first read
+    online: Option<String>,
+}
+
+impl CpuTopology {
+    pub fn new() -> Self {
+        CpuTopology {
+            cpus: Vec::new(),
+            cores: Vec::new(),
+            smt_active: None,
+            online: None,
+        }
+    }
+
+    // Re-read the topology only when CPUs were hotplugged; this also avoids rescanning
+    // every tick where sysfs topology is empty or partial (containers, some VMs)
+    pub fn refresh(&mut self) {
+        let online = online_mask();
+        if self.online.as_ref() != Some(&online) {
+            *self = CpuTopology::read();
+            self.online = Some(online);
+        }
+    }
+
+    fn read() -> Self {
+        let cpu_path = Path::new(CPU_PATH);
+        let mut topology = CpuTopology::new();
+
+        if let Ok(entries) = std::fs::read_dir(cpu_path) {
+            for entry in entries.filter_map(Result::ok) {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let Some(id) = name
+                    .strip_prefix("cpu")
+                    .and_then(|id| id.parse::<usize>().ok())
+                else {
+                    continue;
+                };
+
+                // Offline CPUs have no topology directory
+                let topology_dir = entry.path().join("topology");
+                let Some(core_id) = read_id(&topology_dir.join("core_id")) else {
+                    continue;
+                };
+
+                topology.cpus.push(LogicalCpu {
+                    id,
+                    // die_id and cluster_id only exist on newer kernels
+                    package_id: read_id(&topology_dir.join("physical_package_id")).unwrap_or(0),
+                    die_id: read_id(&topology_dir.join("die_id")).unwrap_or(0),
+                    cluster_id: read_id(&topology_dir.join("cluster_id")).unwrap_or(0),
+                    core_id,
+                });
+            }
+        }
+        topology.cpus.sort_by_key(|cpu| cpu.id);
+
+        // Group logical CPUs that share a physical core
+        for cpu in &topology.cpus {
+            match topology.cores.iter_mut().find(|core| {
+                core.package_id == cpu.package_id
+                    && core.die_id == cpu.die_id
+                    && core.cluster_id == cpu.cluster_id
+                    && core.core_id == cpu.core_id
+            }) {
+                Some(core) => core.threads.push(cpu.id),
+                None => topology.cores.push(PhysicalCore {
+                    package_id: cpu.package_id,
+                    die_id: cpu.die_id,
+                    cluster_id: cpu.cluster_id,
+                    core_id: cpu.core_id,
+                    threads: vec![cpu.id],
+                }),
+            }
+        }
+
+        topology.smt_active = read_file_to_string(&cpu_path.join("smt/active"))
+            .ok()
+            .map(|active| active.trim() == "1");
+
+        topology
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cpus.is_empty()
+    }
+
+    pub fn socket_count(&self) -> usize {
+        let mut packages: Vec<i32> = self.cpus.iter().map(|cpu| cpu.package_id).collect();
+        packages.sort();
+        packages.dedup();
+        packages.len()
+    }
+
+    pub fn die_count(&self) -> usize {
+        let mut dies: Vec<(i32, i32)> = self
+            .cpus
+            .iter()
+            .map(|cpu| (cpu.package_id, cpu.die_id))
+            .collect();
+        dies.sort();
+        dies.dedup();
+        dies.len()
+    }
+
+    pub fn core_count(&self) -> usize {
+        self.cores.len()
+    }
+}
+
+// Empty when the file is missing, which then only triggers the first read
+fn online_mask() -> String {
+    read_file_to_string(&Path::new(CPU_PATH).join("online"))
+        .map(|online| online.trim().to_string())
+        .unwrap_or_default()
+}
+
+fn read_id(path: &Path) -> Option<i32> {
+    read_file_to_string(path).ok()?.trim().parse::<i32>().ok()
+}
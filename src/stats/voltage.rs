@@ -0,0 +1,180 @@
+use crate::{
+    common::SysWrapper,
+    renderer::core::Mouse,
+    stats::hwmon::{Sensor, SensorKind},
+};
+use ratatui::{
+    style::{Color, Style},
+    text::Line,
+};
+
+#[derive(Clone)]
+pub struct VoltageStats {
+    chips: Vec<VoltageChip>,
+    // Allowed deviation from a rail's nominal voltage, in percent
+    tolerance: Option<f64>,
+}
+
+#[derive(Clone)]
+pub struct VoltageChip {
+    pub name: String,
+    pub rails: Vec<VoltageRail>,
+}
+
+#[derive(Clone)]
+pub struct VoltageRail {
+    // Min/max come from the hwmon session tracking
+    pub sensor: Sensor,
+    // Parsed from labels like "+12V", "+3.3V" or "5VSB"
+    pub nominal: Option<f64>,
+}
+
+impl VoltageRail {
+    // Reason the reading is flagged, if any; chip limits take precedence when the BIOS
+    // programmed them (see Sensor::discover)
+    pub fn fault(&self, tolerance: Option<f64>) -> Option<String> {
+        let sensor = &self.sensor;
+        if sensor.min_limit.is_some_and(|min| sensor.value < min) {
+            return Some(String::from("LOW"));
+        }
+        if sensor.max_limit.is_some_and(|max| sensor.value > max) {
+            return Some(String::from("HIGH"));
+        }
+
+        let (nominal, tolerance) = (self.nominal?, tolerance?);
+        let deviation = (sensor.value - nominal) / nominal * 100.0;
+        if deviation.abs() > tolerance {
+            return Some(format!("{:+.1}%", deviation));
+        }
+        None
+    }
+}
+
+impl VoltageStats {
+    pub fn new(tolerance: Option<f64>) -> Self {
+        VoltageStats {
+            chips: Vec::new(),
+            tolerance,
+        }
+    }
+
+    pub fn update(&mut self, sys_obj: &mut SysWrapper) {
+        self.chips = sys_obj
+            .hwmon
+            .chips()
+            .iter()
+            .map(|chip| VoltageChip {
+                name: chip.display_name(),
+                rails: chip
+                    .sensors_of(SensorKind::Voltage)
+                    .map(|sensor| VoltageRail {
+                        nominal: nominal_voltage(&sensor.label),
+                        sensor: sensor.clone(),
+                    })
+                    .collect(),
+            })
+            .filter(|chip| !chip.rails.is_empty())
+            .collect();
+    }
+
+    pub fn get_mouse(&self) -> Mouse {
+        let title = String::from("Voltages");
+        let mut mouse = Mouse::new(title);
+
+        if self.chips.is_empty() {
+            mouse.add(String::from("No voltage sensors detected"));
+            return mouse;
+        }
+
+        if let Some(tolerance) = self.tolerance {
+            mouse.add(format!("Tolerance: ±{:.1}%", tolerance));
+        }
+
+        for (i, chip) in self.chips.iter().enumerate() {
+            if i > 0 || self.tolerance.is_some() {
+                mouse.add(String::from("")); // Add empty line between chips
+            }
+            mouse.add(chip.name.clone());
+
+            for rail in &chip.rails {
+                let sensor = &rail.sensor;
+                let mut line = format!(
+                    "  {}: {:.3}V (Min: {:.3}V, Max: {:.3}V)",
+                    sensor.label, sensor.value, sensor.min_value, sensor.max_value
+                );
+                if let (Some(min), Some(max)) = (sensor.min_limit, sensor.max_limit) {
+                    line.push_str(&format!(" Limits: {:.3}-{:.3}V", min, max));
+                }
+
+                match rail.fault(self.tolerance) {
+                    Some(fault) => {
+                        line.push_str(&format!(" [{}]", fault));
+                        mouse.add_line(Line::styled(line, Style::default().fg(Color::Red)));
+                    }
+                    None => mouse.add(line),
+                }
+            }
+        }
+
+        mouse
+    }
+}
+
+// Pull the rail voltage out of a label: the number directly in front of a 'V', plus
+// the digits right after it for the "1V8"/"3V3" style of board rails
+fn nominal_voltage(label: &str) -> Option<f64> {
+    let chars: Vec<char> = label.chars().collect();
+    let v_at = (1..chars.len())
+        .find(|&i| chars[i].eq_ignore_ascii_case(&'v') && chars[i - 1].is_ascii_digit())?;
+
+    let start = chars[..v_at]
+        .iter()
+        .rposition(|c| !c.is_ascii_digit() && *c != '.')
+        .map_or(0, |i| i + 1);
+    let mut number: String = chars[start..v_at].iter().collect();
+    let fraction: String = chars[v_at + 1..]
+        .iter()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    if !fraction.is_empty() && !number.contains('.') {
+        number = format!("{}.{}", number, fraction);
+    }
+    let nominal = number.parse::<f64>().ok().filter(|v| *v > 0.0)?;
+
+    // Super-I/O drivers label the 3.3V rails "3VCC" and "3VSB"
+    if nominal == 3.0 {
+        Some(3.3)
+    } else {
+        Some(nominal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nominal_voltage_from_supply_labels() {
+        assert_eq!(nominal_voltage("+12V"), Some(12.0));
+        assert_eq!(nominal_voltage("+3.3V"), Some(3.3));
+        assert_eq!(nominal_voltage("5VSB"), Some(5.0));
+        assert_eq!(nominal_voltage("3VCC"), Some(3.3));
+        assert_eq!(nominal_voltage("3VSB"), Some(3.3));
+    }
+
+    #[test]
+    fn nominal_voltage_from_board_rail_labels() {
+        assert_eq!(nominal_voltage("VDD_1V8"), Some(1.8));
+        assert_eq!(nominal_voltage("1V05"), Some(1.05));
+        assert_eq!(nominal_voltage("3V3"), Some(3.3));
+        assert_eq!(nominal_voltage("vddq_1v2"), Some(1.2));
+    }
+
+    #[test]
+    fn nominal_voltage_without_a_rail() {
+        assert_eq!(nominal_voltage("Vcore"), None);
+        assert_eq!(nominal_voltage("in0"), None);
+        assert_eq!(nominal_voltage("VIN1"), None);
+        assert_eq!(nominal_voltage(""), None);
+    }
+}